@@ -0,0 +1,171 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A global allocator which counts allocations, for [`count_allocations`].
+///
+/// Installed by building with `--features count-allocations`. Each thread counts its own
+/// allocations, so solves on other threads are not included.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// This thread's allocations. `live` is signed, as memory may be freed by another thread
+/// than the one which allocated it.
+#[derive(Copy, Clone, Default)]
+struct Counters {
+    count: u64,
+    bytes: u64,
+    live: isize,
+    peak: isize,
+}
+
+thread_local! {
+    // Neither initializing nor dropping a `Cell` of a `Copy` type allocates.
+    static COUNTERS: Cell<Counters> = Cell::new(Counters::default());
+}
+
+/// Update this thread's counters; does nothing once the thread is being torn down.
+fn update(f: impl FnOnce(&mut Counters)) {
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        f(&mut c);
+        counters.set(c);
+    });
+}
+
+impl CountingAllocator {
+    fn allocated(size: usize) {
+        INSTALLED.store(true, Ordering::Relaxed);
+        update(|c| {
+            c.count += 1;
+            c.bytes += size as u64;
+            c.live += size as isize;
+            c.peak = c.peak.max(c.live);
+        });
+    }
+    fn freed(size: usize) {
+        update(|c| c.live -= size as isize);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::freed(layout.size());
+    }
+    /// Counted as a new allocation of `new_size` bytes, replacing the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::freed(layout.size());
+            Self::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// The allocations made while running a function.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct AllocStats {
+    /// The number of allocations, including reallocations.
+    pub count: u64,
+    /// The total size of every allocation.
+    pub bytes: u64,
+    /// The most bytes which were live at once, beyond those live before the function.
+    pub peak: usize,
+}
+
+/// Run `f`, counting its allocations if the [`CountingAllocator`] is installed.
+///
+/// Only allocations on the calling thread are counted, so other threads may solve at the
+/// same time. Calls may be nested; each counts everything allocated inside it.
+pub fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    let mut before = Counters::default();
+    update(|c| {
+        before = *c;
+        c.peak = c.live;
+    });
+    let value = f();
+    let mut stats = AllocStats::default();
+    update(|c| {
+        stats = AllocStats {
+            count: c.count - before.count,
+            bytes: c.bytes - before.bytes,
+            peak: (c.peak - before.live).max(0) as usize,
+        };
+        // An enclosing call still sees the highest peak.
+        c.peak = c.peak.max(before.peak);
+    });
+    (value, INSTALLED.load(Ordering::Relaxed).then(|| stats))
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations | {} allocated | peak {} live",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak as u64)
+        )
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=9999 => format!("{}B", bytes),
+        10_000..=9_999_999 => format!("{:.1}KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1}MiB", bytes as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+#[test]
+fn test_count_allocations() {
+    let (v, stats) = count_allocations(|| vec![0u8; 100]);
+    assert_eq!(stats.is_some(), cfg!(feature = "count-allocations"));
+    if let Some(stats) = stats {
+        assert!(stats.count >= 1 && stats.bytes >= 100 && stats.peak >= 100);
+    }
+    drop(v);
+    // Allocations on other threads are not counted.
+    let (_, stats) = count_allocations(|| {
+        std::thread::spawn(|| vec![0u8; 1 << 20]).join().unwrap();
+    });
+    if let Some(stats) = stats {
+        assert!(stats.bytes < 1 << 20, "{:?}", stats);
+    }
+    let ((_, inner), outer) = count_allocations(|| {
+        let v = vec![0u8; 1000];
+        drop(v);
+        count_allocations(|| vec![0u8; 10])
+    });
+    if let (Some(inner), Some(outer)) = (inner, outer) {
+        assert!(inner.peak >= 10 && inner.peak < 1000);
+        assert!(outer.peak >= 1000 && outer.count > inner.count);
+    }
+    assert_eq!(format_bytes(2048), "2048B");
+    assert_eq!(format_bytes(20480), "20.0KiB");
+}
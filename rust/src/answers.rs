@@ -0,0 +1,246 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, ensure, Context, Result};
+
+use crate::input;
+
+/// The directory used for known answers when `AOC_ANSWERS_DIR` is not set.
+pub const DEFAULT_ANSWERS_DIR: &str = "answers";
+
+/// The accepted answers for a day's real input, as they would be printed.
+///
+/// Stored as a small TOML file:
+///
+/// ```toml
+/// part1 = "1233"
+/// part2 = "1275"
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct KnownAnswers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// The result of checking an answer against a [`KnownAnswers`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// No answer is known for this part.
+    Unknown,
+}
+
+impl KnownAnswers {
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut answers = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .with_context(|| format!("line {}: expected 'key = \"value\"'", i + 1))?;
+            let value = unquote(value.trim()).with_context(|| format!("line {}", i + 1))?;
+            match key.trim() {
+                "part1" => answers.part1 = Some(value),
+                "part2" => answers.part2 = Some(value),
+                key => bail!("line {}: unknown key '{}'", i + 1, key),
+            }
+        }
+        Ok(answers)
+    }
+    pub fn get(&self, level: u8) -> Option<&str> {
+        match level {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+    pub fn set(&mut self, level: u8, answer: impl Into<String>) {
+        match level {
+            1 => self.part1 = Some(answer.into()),
+            2 => self.part2 = Some(answer.into()),
+            _ => panic!("invalid part {}", level),
+        }
+    }
+    /// Compare an answer for the given part (1 or 2) with the known answer.
+    pub fn check(&self, level: u8, answer: &str) -> Verdict {
+        match self.get(level) {
+            Some(known) if known == answer => Verdict::Pass,
+            Some(known) => Verdict::Fail {
+                expected: known.to_owned(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for KnownAnswers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(part1) = &self.part1 {
+            writeln!(f, "part1 = {}", quote(part1))?;
+        }
+        if let Some(part2) = &self.part2 {
+            writeln!(f, "part2 = {}", quote(part2))?;
+        }
+        Ok(())
+    }
+}
+
+impl Verdict {
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Self::Fail { .. })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Fail { expected } if expected.contains('\n') => {
+                write!(f, "FAIL (expected:\n{})", expected)
+            }
+            Self::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Self::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// The known answers for every day, stored as `{dir}/YYYY/dayNN.toml`.
+#[derive(Clone, Debug)]
+pub struct AnswerRegistry {
+    dir: PathBuf,
+}
+
+impl AnswerRegistry {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+    /// Use the directory in `AOC_ANSWERS_DIR` (which may be set in a `.env` file),
+    /// or [`DEFAULT_ANSWERS_DIR`].
+    pub fn from_env() -> Self {
+        input::load_dotenv();
+        let dir = std::env::var_os("AOC_ANSWERS_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_ANSWERS_DIR));
+        Self::new(dir)
+    }
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{:02}.toml", day))
+    }
+    /// The known answers for the given day; empty if none were recorded.
+    pub fn load(&self, year: u16, day: u8) -> Result<KnownAnswers> {
+        let path = self.path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(data) => KnownAnswers::parse(&data)
+                .with_context(|| format!("failed to parse {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(KnownAnswers::default()),
+            Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+    pub fn store(&self, year: u16, day: u8, answers: &KnownAnswers) -> Result<()> {
+        let path = self.path(year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, answers.to_string())
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+    /// Record the answers which were run as the known answers, keeping any others.
+    pub fn lock(&self, year: u16, day: u8, answers: &[Option<String>; 2]) -> Result<()> {
+        for (level, answer) in (1..=2).zip(answers) {
+            if let Some(answer) = answer {
+                self.record(year, day, level, answer)?;
+            }
+        }
+        Ok(())
+    }
+    /// Check the answers which were run against the known answers.
+    pub fn verify(
+        &self,
+        year: u16,
+        day: u8,
+        answers: &[Option<String>; 2],
+    ) -> Result<[Option<Verdict>; 2]> {
+        let known = self.load(year, day)?;
+        let check = |level, answer: &Option<String>| {
+            answer.as_ref().map(|answer| known.check(level, answer))
+        };
+        Ok([check(1, &answers[0]), check(2, &answers[1])])
+    }
+    /// Record the accepted answer for one part, keeping the other part as it was.
+    pub fn record(&self, year: u16, day: u8, level: u8, answer: &str) -> Result<()> {
+        ensure!(level == 1 || level == 2, "invalid part {}", level);
+        let mut answers = self.load(year, day)?;
+        answers.set(level, answer);
+        self.store(year, day, &answers)
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn unquote(s: &str) -> Result<String> {
+    let inner = s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .context("expected a quoted string")?;
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => unquoted.push('"'),
+            Some('\\') => unquoted.push('\\'),
+            Some('n') => unquoted.push('\n'),
+            Some('t') => unquoted.push('\t'),
+            c => bail!("invalid escape '\\{}'", c.unwrap_or(' ')),
+        }
+    }
+    Ok(unquoted)
+}
+
+#[test]
+fn test_known_answers() {
+    let answers = KnownAnswers {
+        part1: Some("17".into()),
+        part2: Some("█ \"█\"\n█\\█".into()),
+    };
+    assert_eq!(KnownAnswers::parse(&answers.to_string()).unwrap(), answers);
+    assert_eq!(answers.check(1, "17"), Verdict::Pass);
+    assert!(answers.check(1, "18").is_fail());
+
+    let answers = KnownAnswers::parse("# day 1\npart2 = \"5\"\n").unwrap();
+    assert_eq!(answers.check(1, "7"), Verdict::Unknown);
+    assert_eq!(answers.check(2, "5"), Verdict::Pass);
+    assert!(KnownAnswers::parse("part3 = \"1\"").is_err());
+    assert!(KnownAnswers::parse("part1 = 1").is_err());
+}
@@ -0,0 +1,144 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use anyhow::{ensure, Result};
+
+/// How long and how often to run a benchmark.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BenchConfig {
+    /// Runs during this time are not measured.
+    pub warmup: Duration,
+    /// Measure exactly this many runs, instead of running for `time`.
+    pub iterations: Option<usize>,
+    /// Measure as many runs as fit in this time.
+    pub time: Duration,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(500),
+            iterations: None,
+            time: Duration::from_secs(3),
+        }
+    }
+}
+
+/// Summary statistics of the measured runs.
+#[derive(Copy, Clone, Debug)]
+pub struct BenchStats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    /// The size of the input handled by each run.
+    pub input_len: usize,
+}
+
+impl BenchStats {
+    pub fn from_samples(samples: &mut [Duration], input_len: usize) -> Result<Self> {
+        ensure!(!samples.is_empty(), "no runs were measured");
+        samples.sort_unstable();
+        let runs = samples.len();
+        let median = if runs % 2 == 0 {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / (runs.max(2) - 1) as f64;
+        Ok(Self {
+            runs,
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            input_len,
+        })
+    }
+    /// Runs per second, based on the mean.
+    pub fn runs_per_sec(&self) -> f64 {
+        1.0 / self.mean.as_secs_f64()
+    }
+    /// Input megabytes per second, based on the mean.
+    pub fn mb_per_sec(&self) -> f64 {
+        self.input_len as f64 / 1_000_000.0 / self.mean.as_secs_f64()
+    }
+}
+
+impl fmt::Display for BenchStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "min {:.2?} | median {:.2?} | mean {:.2?} | stddev {:.2?}",
+            self.min, self.median, self.mean, self.stddev
+        )?;
+        write!(
+            f,
+            "{} runs | {:.1} runs/s | {:.2} MB/s",
+            self.runs,
+            self.runs_per_sec(),
+            self.mb_per_sec()
+        )
+    }
+}
+
+/// Repeatedly solve `input`, measuring only the calls to `solve`.
+///
+/// The input is cloned for every run, outside of the measured time.
+pub fn bench<T>(
+    config: &BenchConfig,
+    input: &str,
+    mut solve: impl FnMut(String) -> Result<T>,
+) -> Result<BenchStats> {
+    let mut run = || {
+        let input = input.to_owned();
+        let time = Instant::now();
+        solve(input)?;
+        Ok::<_, anyhow::Error>(time.elapsed())
+    };
+
+    let warmup = Instant::now();
+    while warmup.elapsed() < config.warmup {
+        run()?;
+    }
+
+    let mut samples = vec![];
+    match config.iterations {
+        Some(iterations) => {
+            for _ in 0..iterations {
+                samples.push(run()?);
+            }
+        }
+        None => {
+            let start = Instant::now();
+            while samples.is_empty() || start.elapsed() < config.time {
+                samples.push(run()?);
+            }
+        }
+    }
+    BenchStats::from_samples(&mut samples, input.len())
+}
+
+#[test]
+fn test_stats() {
+    let mut samples: Vec<_> = [4, 1, 3, 2]
+        .iter()
+        .map(|&n| Duration::from_millis(n))
+        .collect();
+    let stats = BenchStats::from_samples(&mut samples, 1_000_000).unwrap();
+    assert_eq!(stats.runs, 4);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_micros(2500));
+    assert_eq!(stats.mean, Duration::from_micros(2500));
+    assert_eq!(stats.stddev.as_micros(), 1290);
+    assert!((stats.mb_per_sec() - 400.0).abs() < 1e-6);
+    assert!(BenchStats::from_samples(&mut [], 0).is_err());
+}
@@ -0,0 +1,236 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, ensure, Context, Result};
+use aoc2021::{
+    cli::{format_table, value},
+    days, Format, Normalize, Options, Profile, Record, Verdict,
+};
+
+const USAGE: &str = "\
+Usage: aoc [DAYS] [OPTIONS]
+
+Runs the selected days of one year on their real inputs and prints a summary.
+DAYS may be a day (7), a range (3..=9 or 3..10) or a list (1,4,7). Defaults to every day.
+
+Options:
+  -r, --refresh           Download the real inputs even if they are cached
+  -y, --year <YEAR>       Run the days of this year (default 2021)
+  -v, --verify            Check the answers against answers/YYYY/dayNN.toml
+  -j, --jobs <N>          Solve up to N days at once (default: one per CPU)
+      --sequential        Solve one day at a time, for timings without contention
+      --profile <NAME>    Use the session cookie, inputs and answers of profile NAME
+      --compare           Run each day with every profile in AOC_PROFILES, side by side
+  -t, --trace <LEVEL>     Print the solvers' traces up to LEVEL: info, debug or trace
+  -w, --watch             Solve one day on its input and saved examples whenever they change
+      --raw               Solve the inputs exactly as read, without normalizing them
+  -f, --format <FORMAT>   Print a table (text, default), or one record per day as json or csv
+  -h, --help              Print this message";
+
+fn main() -> Result<()> {
+    let mut selection = vec![];
+    let mut year = aoc2021::DEFAULT_YEAR;
+    let mut jobs = None;
+    let mut compare = false;
+    let mut watch = false;
+    let mut options = Options::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            "-r" | "--refresh" => options.refresh = true,
+            "-v" | "--verify" => options.verify = true,
+            "--raw" => options.normalize = Normalize::RAW,
+            "-t" | "--trace" => options.trace = Some(value(&mut args, &arg)?.parse()?),
+            "--profile" => options.profile = Some(Profile::new(value(&mut args, &arg)?)?),
+            "--compare" => compare = true,
+            "-w" | "--watch" => watch = true,
+            "-j" | "--jobs" => {
+                let value = value(&mut args, &arg)?;
+                let n = value
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .with_context(|| format!("invalid number of jobs '{}'", value))?;
+                jobs = Some(n);
+            }
+            "--sequential" => jobs = Some(1),
+            "-f" | "--format" => options.format = value(&mut args, &arg)?.parse()?,
+            "-y" | "--year" => {
+                let value = value(&mut args, &arg)?;
+                year = value
+                    .parse()
+                    .with_context(|| format!("invalid year '{}'", value))?
+            }
+            _ if arg.starts_with('-') => bail!("unexpected argument '{}'\n\n{}", arg, USAGE),
+            _ => selection.push(arg),
+        }
+    }
+    let days = if selection.is_empty() {
+        days::of_year(year)
+    } else {
+        days::parse_selection(year, &selection.join(","))?
+    };
+    ensure!(!days.is_empty(), "no days are implemented for {}", year);
+
+    if watch {
+        ensure!(
+            days.len() == 1,
+            "--watch needs exactly one day, such as 'aoc 7 --watch'"
+        );
+        ensure!(
+            !compare && options.format == Format::Text,
+            "--watch only supports text output for one profile"
+        );
+        if options.trace.is_some() {
+            aoc2021::trace::set_level(options.trace);
+        }
+        return aoc2021::watch(days[0], &options, aoc2021::DEFAULT_WATCH_INTERVAL);
+    }
+
+    let profiles = match compare {
+        true => {
+            ensure!(
+                options.profile.is_none(),
+                "--compare runs every profile, so --profile cannot be given"
+            );
+            ensure!(
+                options.format == Format::Text,
+                "--compare only supports text output"
+            );
+            Profile::from_env()?
+                .into_iter()
+                .map(|profile| Options {
+                    profile: Some(profile),
+                    ..options.clone()
+                })
+                .collect()
+        }
+        false => vec![options.clone()],
+    };
+    let runs: Vec<(days::Day, Options)> = days
+        .iter()
+        .flat_map(|&day| profiles.iter().map(move |options| (day, options.clone())))
+        .collect();
+    // The profile of each record, when comparing.
+    let names: Vec<&str> = match compare {
+        true => runs
+            .iter()
+            .map(|(_, options)| options.profile.as_ref().map_or("", |p| p.name.as_str()))
+            .collect(),
+        false => vec![],
+    };
+
+    if options.trace.is_some() {
+        aoc2021::trace::set_level(options.trace);
+    }
+    let jobs = jobs.unwrap_or_else(days::default_jobs);
+    let wall_time = Instant::now();
+    let mut records = days::record_each(&runs, jobs);
+    // Days were timed side by side, so one over its budget gets a few runs on its own.
+    if !cfg!(debug_assertions) {
+        days::retime_over_budget(&runs, &mut records);
+    }
+    let wall_time = wall_time.elapsed();
+
+    if options.format == Format::Text {
+        print_table(&records, &names, options.verify);
+        println!(
+            "({}, {}, wall time {} with {} jobs)",
+            year,
+            aoc2021::profile(),
+            format_time(wall_time),
+            jobs.min(records.len())
+        );
+        for (i, record) in records.iter().enumerate() {
+            let name = match names.get(i) {
+                Some(profile) => format!("Day {:02} ({})", record.day, profile),
+                None => format!("Day {:02}", record.day),
+            };
+            if let Some(error) = &record.error {
+                eprintln!("{} failed: {}", name, error);
+            }
+            for (level, verdict) in (1..=2).zip(&record.verdicts) {
+                if let Some(verdict) = verdict.as_ref().filter(|v| v.is_fail()) {
+                    eprintln!("{} - Part {}: {}", name, level, verdict);
+                }
+            }
+            if let Some(budget) = record.budget.filter(|_| record.over_budget()) {
+                let warn = if cfg!(debug_assertions) { "WARN: " } else { "" };
+                eprintln!(
+                    "{}{} took longer than its release budget of {}",
+                    warn,
+                    name,
+                    format_time(budget)
+                );
+            }
+        }
+    } else {
+        aoc2021::print_records(options.format, &records)?;
+    }
+
+    let failed = records.iter().filter(|r| r.failed()).count();
+    if failed > 0 {
+        bail!("{} of {} runs failed", failed, records.len());
+    }
+    Ok(())
+}
+
+/// `profiles` has the profile of each record, or is empty to leave out the column.
+fn print_table(records: &[Record], profiles: &[&str], verify: bool) {
+    // (header, right aligned)
+    let mut columns = vec![("Day", true)];
+    if !profiles.is_empty() {
+        columns.push(("Profile", false));
+    }
+    columns.extend([("Part 1", false), ("Part 2", false)]);
+    if verify {
+        columns.push(("Verify", false));
+    }
+    columns.push(("Time", true));
+
+    let time = |record: &Record| record.timings.map_or(Duration::ZERO, |t| t.total);
+    let table: Vec<Vec<String>> = records
+        .iter()
+        .enumerate()
+        .map(|(i, record)| {
+            let answer = |part: &Option<String>| match (part, &record.error) {
+                (Some(answer), _) => answer.clone(),
+                (None, Some(_)) => "error".into(),
+                (None, None) => "-".into(),
+            };
+            let mut cells = vec![record.day.to_string()];
+            if let Some(profile) = profiles.get(i) {
+                cells.push(profile.to_string());
+            }
+            cells.extend([answer(&record.part1), answer(&record.part2)]);
+            if verify {
+                cells.push(match &record.verdicts {
+                    [None, None] => "-".into(),
+                    [v1, v2] => format!("{}/{}", short_verdict(v1), short_verdict(v2)),
+                });
+            }
+            cells.push(format_time(time(record)));
+            cells
+        })
+        .collect();
+    let total = records.iter().map(time).sum();
+    let mut total_row = vec![String::new(); columns.len()];
+    total_row[0] = "Total".into();
+    total_row[columns.len() - 1] = format_time(total);
+    print!("{}", format_table(&columns, &table, Some(&total_row)));
+}
+
+fn short_verdict(verdict: &Option<Verdict>) -> &'static str {
+    match verdict {
+        Some(Verdict::Pass) => "PASS",
+        Some(Verdict::Fail { .. }) => "FAIL",
+        Some(Verdict::Unknown) | None => "-",
+    }
+}
+
+fn format_time(time: Duration) -> String {
+    format!("{:.3}ms", time.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,196 @@
+use std::{fs, time::Duration};
+
+use anyhow::{bail, ensure, Context, Result};
+use aoc2021::{
+    cli::{format_table, value},
+    Leaderboard,
+};
+
+const USAGE: &str = "\
+Usage: leaderboard <FILE> [OPTIONS]
+       leaderboard --fetch <ID> [OPTIONS]
+
+Prints reports from a private leaderboard's JSON export: each member's star times, the
+standings after each day, and how hard each day was. Times are from the puzzle unlocking.
+
+Options:
+      --fetch <ID>        Download leaderboard ID with the session cookie, instead of reading FILE
+  -y, --year <YEAR>       The event to download with --fetch (default 2021)
+  -o, --output <FILE>     Save the downloaded JSON to FILE
+  -r, --report <REPORT>   Only print one report: stars, standings or days
+  -h, --help              Print this message";
+
+fn main() -> Result<()> {
+    let mut path = None;
+    let mut fetch = None;
+    let mut output = None;
+    let mut report = None;
+    let mut year = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            "--fetch" => {
+                let value = value(&mut args, &arg)?;
+                let id: u64 = value
+                    .parse()
+                    .with_context(|| format!("invalid leaderboard id '{}'", value))?;
+                fetch = Some(id);
+            }
+            "-y" | "--year" => {
+                let value = value(&mut args, &arg)?;
+                year = Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("invalid year '{}'", value))?,
+                )
+            }
+            "-o" | "--output" => output = Some(value(&mut args, &arg)?),
+            "-r" | "--report" => {
+                let value = value(&mut args, &arg)?;
+                ensure!(
+                    ["stars", "standings", "days"].contains(&value.as_str()),
+                    "invalid report '{}', expected stars, standings or days",
+                    value
+                );
+                report = Some(value);
+            }
+            _ if arg.starts_with('-') => bail!("unexpected argument '{}'\n\n{}", arg, USAGE),
+            _ if path.is_none() => path = Some(arg),
+            _ => bail!("more than one file given\n\n{}", USAGE),
+        }
+    }
+
+    let leaderboard = match (fetch, path) {
+        (Some(id), None) => {
+            let year = year.unwrap_or(aoc2021::DEFAULT_YEAR);
+            let json = aoc2021::download_leaderboard(year, id)
+                .with_context(|| format!("failed to download leaderboard {}", id))?;
+            if let Some(output) = &output {
+                fs::write(output, &json).with_context(|| format!("failed to write {}", output))?;
+            }
+            Leaderboard::parse(&json).context("failed to parse the downloaded leaderboard")?
+        }
+        (None, Some(path)) => {
+            ensure!(output.is_none(), "--output only applies to --fetch");
+            // The export names its own event.
+            ensure!(year.is_none(), "--year only applies to --fetch");
+            Leaderboard::from_file(path)?
+        }
+        _ => bail!("expected either a FILE or --fetch\n\n{}", USAGE),
+    };
+
+    let show = |name| report.as_deref().map_or(true, |report| report == name);
+    if show("stars") {
+        print_stars(&leaderboard);
+    }
+    if show("standings") {
+        print_standings(&leaderboard);
+    }
+    if show("days") {
+        print_days(&leaderboard);
+    }
+    Ok(())
+}
+
+fn print_stars(leaderboard: &Leaderboard) {
+    let mut members: Vec<_> = leaderboard.members.iter().collect();
+    members.sort_by_key(|m| std::cmp::Reverse(m.local_score));
+    let mut rows = vec![];
+    for member in members {
+        for (i, &day) in member.star_times.keys().enumerate() {
+            let time = |level| format_duration(leaderboard.solve_time(member, day, level));
+            rows.push(vec![
+                match i {
+                    0 => member.display_name(),
+                    _ => String::new(),
+                },
+                day.to_string(),
+                time(1),
+                time(2),
+                format_duration(member.part2_delay(day)),
+            ]);
+        }
+    }
+    print_table(&["Member", "Day", "Part 1", "Part 2", "Delay"], &rows);
+}
+
+fn print_standings(leaderboard: &Leaderboard) {
+    let standings = leaderboard.standings();
+    let last = match standings.last() {
+        Some(last) => last,
+        None => return,
+    };
+    let days: Vec<String> = standings.iter().map(|s| s.day.to_string()).collect();
+    let mut columns = vec!["Member", "Score"];
+    columns.extend(days.iter().map(String::as_str));
+    let rows: Vec<Vec<String>> = last
+        .scores
+        .iter()
+        .map(|&(member, score)| {
+            let mut cells = vec![
+                leaderboard.members[member].display_name(),
+                score.to_string(),
+            ];
+            cells.extend(standings.iter().map(|s| {
+                let rank = s.scores.iter().position(|&(m, _)| m == member);
+                rank.map_or(String::new(), |rank| (rank + 1).to_string())
+            }));
+            cells
+        })
+        .collect();
+    println!("Rank after each day:");
+    print_table(&columns, &rows);
+}
+
+fn print_days(leaderboard: &Leaderboard) {
+    let rows: Vec<Vec<String>> = leaderboard
+        .difficulty()
+        .iter()
+        .map(|stats| {
+            vec![
+                stats.day.to_string(),
+                stats.stars[0].to_string(),
+                stats.stars[1].to_string(),
+                format_duration(stats.median_times[0]),
+                format_duration(stats.median_times[1]),
+                format_duration(stats.median_delay),
+            ]
+        })
+        .collect();
+    print_table(
+        &[
+            "Day",
+            "Part 1 stars",
+            "Part 2 stars",
+            "Median part 1",
+            "Median part 2",
+            "Median delay",
+        ],
+        &rows,
+    );
+}
+
+/// Print a table with the first column left aligned and the others right aligned.
+fn print_table(columns: &[&str], rows: &[Vec<String>]) {
+    let columns: Vec<(&str, bool)> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, &header)| (header, i > 0))
+        .collect();
+    println!("{}", format_table(&columns, rows, None));
+}
+
+/// `H:MM:SS`, or `-` for a star which was not earned.
+fn format_duration(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => {
+            let secs = duration.as_secs();
+            format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+        }
+        None => "-".into(),
+    }
+}
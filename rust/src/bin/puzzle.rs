@@ -0,0 +1,64 @@
+use anyhow::{bail, Context, Result};
+use aoc2021::{
+    cli::{parse_day, value},
+    InputCache, Profile,
+};
+
+const USAGE: &str = "\
+Usage: puzzle <DAY> [OPTIONS]
+
+Prints a day's puzzle description as text. The page is downloaded once and cached next
+to the input; a correct answer submitted with --submit clears it, so part 2 is shown once
+it is unlocked.
+
+Options:
+  -y, --year <YEAR>       The event of the puzzle (default 2021)
+      --profile <NAME>    Use the session cookie and input cache of profile NAME
+      --refresh           Download the page again, such as after solving part 1 on the site
+      --color             Show code and emphasis with terminal colors
+  -h, --help              Print this message";
+
+fn main() -> Result<()> {
+    let mut day = None;
+    let mut year = aoc2021::DEFAULT_YEAR;
+    let mut profile = None;
+    let mut refresh = false;
+    let mut color = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            "-y" | "--year" => {
+                let value = value(&mut args, &arg)?;
+                year = value
+                    .parse()
+                    .with_context(|| format!("invalid year '{}'", value))?
+            }
+            "--profile" => profile = Some(Profile::new(value(&mut args, &arg)?)?),
+            "--refresh" => refresh = true,
+            "--color" => color = true,
+            _ if arg.starts_with('-') => bail!("unexpected argument '{}'\n\n{}", arg, USAGE),
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => bail!("more than one day given\n\n{}", USAGE),
+        }
+    }
+    let day = match day {
+        Some(day) => day,
+        None => bail!("expected a day\n\n{}", USAGE),
+    };
+
+    let cache = match &profile {
+        Some(profile) => profile.input_cache(),
+        None => InputCache::from_env(),
+    };
+    let refresh = refresh || cache.refresh();
+    let cache = cache.with_refresh(refresh);
+    let html = cache
+        .get_puzzle(year, day)
+        .with_context(|| format!("failed to retrieve the puzzle for {} Day {:02}", year, day))?;
+    print!("{}", aoc2021::render_puzzle(&html, color));
+    Ok(())
+}
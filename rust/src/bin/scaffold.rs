@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use aoc2021::{
+    cli::{parse_day, value},
+    InputCache, PuzzlePage, Scaffold,
+};
+
+const USAGE: &str = "\
+Usage: scaffold <DAY> [OPTIONS]
+
+Creates src/days/dayN.rs with an unsolved day!, and src/bin/dayN.rs to run it, and adds
+the day to src/days/mod.rs. Existing files are never overwritten.
+
+Options:
+      --page <FILE>       Take the example and its answers from a saved puzzle page
+      --fetch             Take them from the puzzle page, downloaded once and cached
+      --types <T1,T2>     The types of the answers to each part (default usize,usize)
+  -h, --help              Print this message";
+
+fn main() -> Result<()> {
+    let mut day = None;
+    let mut page = None;
+    let mut fetch = false;
+    let mut types = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            "--page" => page = Some(value(&mut args, &arg)?),
+            "--fetch" => fetch = true,
+            "--types" => {
+                let value = value(&mut args, &arg)?;
+                match value.split_once(',') {
+                    Some((t1, t2)) if !t1.trim().is_empty() && !t2.trim().is_empty() => {
+                        types = Some([t1.trim().to_owned(), t2.trim().to_owned()])
+                    }
+                    _ => bail!("invalid types '{}', expected two like u64,String", value),
+                }
+            }
+            _ if arg.starts_with('-') => bail!("unexpected argument '{}'\n\n{}", arg, USAGE),
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => bail!("more than one day given\n\n{}", USAGE),
+        }
+    }
+    let day = match day {
+        Some(day) => day,
+        None => bail!("expected a day\n\n{}", USAGE),
+    };
+
+    let mut scaffold = Scaffold::new(day);
+    if let Some(types) = types {
+        scaffold.out = types;
+    }
+    let page = match (page, fetch) {
+        (Some(_), true) => bail!("only one of --page and --fetch can be given"),
+        (Some(path), false) => Some(PuzzlePage::from_file(path)?),
+        (None, true) => {
+            let html = InputCache::from_env()
+                .get_puzzle(aoc2021::DEFAULT_YEAR, day)
+                .context("failed to retrieve the puzzle")?;
+            Some(PuzzlePage::parse(&html))
+        }
+        (None, false) => None,
+    };
+    if let Some(page) = page {
+        scaffold = scaffold.with_page(&page);
+        if scaffold.example.is_none() {
+            eprintln!("WARN: the puzzle page has no example");
+        }
+    }
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for path in scaffold.write(root, &InputCache::from_env())? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
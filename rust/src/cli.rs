@@ -0,0 +1,111 @@
+//! Helpers shared by the binaries' argument parsing and output.
+
+use anyhow::{ensure, Context, Result};
+
+/// The value following the option `arg`.
+pub fn value(args: &mut impl Iterator<Item = String>, arg: &str) -> Result<String> {
+    args.next()
+        .with_context(|| format!("missing value for '{}'", arg))
+}
+
+/// A day number, from 1 to 25.
+pub fn parse_day(arg: &str) -> Result<u8> {
+    let n: u8 = arg
+        .parse()
+        .with_context(|| format!("invalid day '{}'", arg))?;
+    ensure!((1..=25).contains(&n), "day must be from 1 to 25, got {}", n);
+    Ok(n)
+}
+
+/// Lay out `rows` under a header line, with each column as wide as its widest cell.
+///
+/// `columns` pairs each header with whether the column is right aligned. A cell may span
+/// several lines. The `footer`, such as a total, is printed after a second separator.
+pub fn format_table(
+    columns: &[(&str, bool)],
+    rows: &[Vec<String>],
+    footer: Option<&[String]>,
+) -> String {
+    let all_rows = || rows.iter().map(Vec::as_slice).chain(footer);
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, (header, _))| {
+            all_rows()
+                .flat_map(|cells| cells[i].lines())
+                .map(|line| line.chars().count())
+                .chain([header.chars().count()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let format_line = |cells: &[&str]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(columns)
+            .zip(&widths)
+            .map(|((cell, &(_, right)), &width)| match right {
+                true => format!("{:>1$}", cell, width),
+                false => format!("{:<1$}", cell, width),
+            })
+            .collect();
+        format!("{}\n", line.join(" | ").trim_end())
+    };
+    let format_row = |cells: &[String]| {
+        let lines: Vec<Vec<&str>> = cells.iter().map(|cell| cell.lines().collect()).collect();
+        let height = lines.iter().map(Vec::len).max().unwrap_or_default().max(1);
+        (0..height)
+            .map(|i| {
+                let line: Vec<&str> = lines
+                    .iter()
+                    .map(|cell| cell.get(i).copied().unwrap_or_default())
+                    .collect();
+                format_line(&line)
+            })
+            .collect::<String>()
+    };
+    let separator = "-".repeat(widths.iter().sum::<usize>() + 3 * (widths.len().max(1) - 1));
+
+    let headers: Vec<&str> = columns.iter().map(|&(header, _)| header).collect();
+    let mut out = format_line(&headers);
+    out.push_str(&separator);
+    out.push('\n');
+    rows.iter()
+        .for_each(|cells| out.push_str(&format_row(cells)));
+    if let Some(footer) = footer {
+        out.push_str(&separator);
+        out.push('\n');
+        out.push_str(&format_row(footer));
+    }
+    out
+}
+
+#[test]
+fn test_format_table() {
+    let rows = vec![
+        vec!["1".into(), "a\nbb".into(), "2ms".into()],
+        vec!["10".into(), "ccc".into(), "30ms".into()],
+    ];
+    let columns = [("Day", true), ("Part 1", false), ("Time", true)];
+    let footer = ["Total".to_owned(), String::new(), "32ms".into()];
+    let expected = [
+        "  Day | Part 1 | Time",
+        "---------------------",
+        "    1 | a      |  2ms",
+        "      | bb     |",
+        "   10 | ccc    | 30ms",
+        "---------------------",
+        "Total |        | 32ms",
+        "",
+    ];
+    assert_eq!(
+        format_table(&columns, &rows, Some(&footer)),
+        expected.join("\n")
+    );
+
+    let table = format_table(&[("Member", false), ("Score", true)], &[], None);
+    assert_eq!(table, "Member | Score\n--------------\n");
+    assert_eq!(parse_day("7").unwrap(), 7);
+    assert!(parse_day("26").is_err());
+    assert!(parse_day("x").is_err());
+}
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use crate::LineReader;
+
+const EXAMPLE: &str = r#"199
+200
+208
+210
+200
+207
+240
+269
+260
+263"#;
+
+#[test]
+fn test_streaming() {
+    let streamed = <Day1 as crate::StreamingSolution>::solve_reader(EXAMPLE.as_bytes());
+    assert_eq!(streamed.unwrap(), (7, 5));
+}
+
+crate::day! {
+    Day1 = day 1 -> (usize, usize), budget Duration::from_millis(1);
+    examples {
+        example: EXAMPLE => (7, 5),
+    }
+    fn solve_reader(reader) {
+        let mut lines = LineReader::new(reader);
+        let mut next = || -> Result<Option<i64>> {
+            match lines.next_line()? {
+                Some(line) => Ok(Some(line.parse(line.text, "a number")?)),
+                None => Ok(None),
+            }
+        };
+
+        let mut last3 = next()?.context("need at least 3 numbers")?;
+        let mut last2 = next()?.context("need at least 3 numbers")?;
+        let mut last1 = next()?.context("need at least 3 numbers")?;
+
+        let mut count1 = (last2 > last3) as usize + (last1 > last2) as usize;
+        let mut count3 = 0;
+
+        while let Some(n) = next()? {
+            count1 += (n > last1) as usize;
+            count3 += (n > last3) as usize;
+
+            last3 = last2;
+            last2 = last1;
+            last1 = n;
+        }
+
+        Ok((count1, count3))
+    }
+}
@@ -0,0 +1,84 @@
+use anyhow::Context;
+
+use crate::LineReader;
+
+const EXAMPLE: &str = r#"[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]
+"#;
+
+#[test]
+fn test_streaming() {
+    let streamed = <Day10 as crate::StreamingSolution>::solve_reader(EXAMPLE.as_bytes());
+    assert_eq!(streamed.unwrap(), (26397, 288957));
+}
+
+#[test]
+fn test_overflow() {
+    assert!(<Day10 as crate::Solution>::solve("(".repeat(40)).is_err());
+}
+
+crate::day! {
+    Day10 = day 10 -> (usize, usize);
+    examples {
+        example: EXAMPLE => (26397, 288957),
+    }
+    /// Only one score per incomplete line is kept, to find the median.
+    fn solve_reader(reader) {
+        let mut lines = LineReader::new(reader);
+        let mut part1 = 0;
+        let mut p2_scores = Vec::<usize>::new();
+        'lines: while let Some(line) = lines.next_line()? {
+            let mut stack = vec![];
+            for (i, b) in line.text.bytes().enumerate() {
+                let error = |expected| line.error(line.text.get(i..).unwrap_or_default(), expected);
+                match b {
+                    b'(' | b'[' | b'{' | b'<' => stack.push(b + 2 - (b == b'(') as u8),
+                    _ => {
+                        let want = stack.pop().ok_or_else(|| error("an opening bracket"))?;
+                        if b != want {
+                            let points = match b {
+                                b')' => 3,
+                                b']' => 57,
+                                b'}' => 1197,
+                                b'>' => 25137,
+                                _ => return Err(error("a bracket").into()),
+                            };
+                            part1 += points;
+                            continue 'lines;
+                        }
+                    }
+                }
+            }
+            let p2_score = stack
+                .into_iter()
+                .rev()
+                .map(|b| match b {
+                    b')' => 1,
+                    b']' => 2,
+                    b'}' => 3,
+                    b'>' => 4,
+                    _ => unreachable!(),
+                })
+                .try_fold(0usize, |score, points| {
+                    score.checked_mul(5)?.checked_add(points)
+                })
+                .context("completion score overflowed")?;
+            p2_scores.push(p2_score);
+        }
+
+        p2_scores.sort_unstable();
+        let &part2 = p2_scores
+            .get(p2_scores.len() / 2)
+            .context("no scores found")?;
+
+        Ok((part1, part2))
+    }
+}
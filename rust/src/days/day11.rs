@@ -0,0 +1,115 @@
+use anyhow::Context;
+
+use crate::{numbered_lines, Ascii, Grid, GridIndex, GridSize, Neighbors};
+
+use std::{collections::HashSet, iter};
+
+const EXAMPLE: &str = r#"5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526
+"#;
+
+crate::day! {
+    Day11 = day 11 -> (usize, usize);
+    examples {
+        example: EXAMPLE => (1656, 195),
+    }
+    fn solve(input) {
+        let mut lines = numbered_lines(&input);
+        let first = lines.next().context("missing input")?;
+        let width = first.text.len();
+        if width == 0 {
+            return Err(first.error(first.text, "a digit").into());
+        }
+        let mut input = vec![];
+        for line in iter::once(first).chain(lines) {
+            for (i, b) in line.text.bytes().enumerate() {
+                let at = line.text.get(i..).unwrap_or_default();
+                input.push(b.to_digit().ok_or_else(|| line.error(at, "a digit"))?);
+            }
+            if line.text.len() != width {
+                let at = line
+                    .text
+                    .get(width.min(line.text.len())..)
+                    .unwrap_or_default();
+                return Err(line.error(at, format!("{} digits per line", width)).into());
+            }
+        }
+        let height = input.len() / width;
+        let size = GridSize { width, height };
+        let mut input = Grid::new(input, size).context("invalid input")?;
+
+        let mut energy_levels = <[HashSet<usize>; 10]>::default();
+        input.iter_copied().enumerate().for_each(|(i, level)| {
+            energy_levels[level as usize].insert(i);
+        });
+
+        let mut part1 = 0;
+        let mut part2 = None;
+
+        let mut step = |n| {
+            let indices = (0..input.len()).map(|index| GridIndex { index, size });
+            indices.clone().for_each(|i| input[i] += 1);
+            let mut flashed = Grid::new(vec![false; input.len()], size).unwrap();
+            let mut flash_count = 0;
+            let mut changed = true;
+            while changed {
+                changed = false;
+                indices.clone().for_each(|i| {
+                    if input[i] > 9 && !flashed[i] {
+                        flash_count += 1;
+                        flashed[i] = true;
+                        changed = true;
+                        i.neighbors_iter(Neighbors::All)
+                            .for_each(|i| input[i] = input[i].saturating_add(1));
+                    }
+                });
+            }
+            input.iter_mut().for_each(|b| {
+                if *b > 9 {
+                    *b = 0
+                }
+            });
+            crate::trace!(Debug, "step {}: {} flashes", n, flash_count);
+            crate::trace!(Trace, "after step {}:\n{}", n, render(&input));
+            flash_count
+        };
+
+        // Real inputs synchronize within a few hundred steps; others may never.
+        const MAX_STEPS: usize = 10_000;
+        for i in 0..MAX_STEPS {
+            let flashes = step(i + 1);
+            if flashes == size.to_len() && part2.is_none() {
+                // index to count
+                part2 = Some(i + 1)
+            }
+            if i < 100 {
+                part1 += flashes;
+            }
+            if i >= 100 && part2.is_some() {
+                break;
+            }
+        }
+        let part2 = part2
+            .with_context(|| format!("octopuses did not all flash within {} steps", MAX_STEPS))?;
+
+        Ok((part1, part2))
+    }
+}
+
+/// The energy levels, one line of digits per row.
+fn render(grid: &Grid<u8>) -> String {
+    let digits: Vec<u8> = grid.iter().map(|&level| b'0' + level).collect();
+    let rows: Vec<&str> = digits
+        .chunks(grid.size().width)
+        .map(|row| std::str::from_utf8(row).unwrap_or_default())
+        .collect();
+    rows.join("\n")
+}
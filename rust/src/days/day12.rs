@@ -0,0 +1,173 @@
+use std::fmt;
+
+use anyhow::{ensure, Context, Result};
+use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+use crate::{numbered_lines, Ascii};
+
+crate::day! {
+    Day12 = day 12 -> (usize, usize);
+    examples {
+        small: r#"start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end
+"# => (10, 36),
+        medium: r#"dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc
+"# => (19, 103),
+        large: r#"fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW
+"# => (226, 3509),
+        direct: "start-end" => (1, _),
+    }
+    fn solve(input) {
+        let start = CaveId::from_str("start").unwrap();
+        let end = CaveId::from_str("end").unwrap();
+
+        let mut paths = HashMap::<CaveId, HashSet<CaveId>>::default();
+        numbered_lines(&input).try_for_each::<_, Result<_>>(|line| {
+            let (a, b) = line.split_once(line.text, "-")?;
+            let cave = |s| CaveId::from_str(s).map_err(|_| line.error(s, "a cave name"));
+            let a = cave(a)?;
+            let b = cave(b)?;
+            // Two connected big caves would allow infinitely many paths.
+            if a.is_big() && b.is_big() {
+                return Err(line.error(line.text, "at most one big cave").into());
+            }
+            paths.entry(a).or_default().insert(b);
+            paths.entry(b).or_default().insert(a);
+            Ok(())
+        })?;
+
+        let mut part1 = 0;
+        let mut part2 = 0;
+
+        let no_paths = HashSet::default();
+        let mut possible = vec![(start, HashSet::default(), false)];
+
+        while let Some((position, mut smalls_visited, have_second_small)) = possible.pop() {
+            for &neighbor in paths.get(&position).unwrap_or(&no_paths) {
+                if neighbor == start {
+                    continue;
+                }
+                if neighbor == end {
+                    if !have_second_small {
+                        part1 += 1;
+                    }
+                    part2 += 1;
+                    continue;
+                }
+
+                let is_small_and_new = neighbor.is_small() && smalls_visited.insert(neighbor);
+                let is_second_small =
+                    neighbor.is_small() && !is_small_and_new && !have_second_small;
+                if neighbor.is_big() || is_small_and_new || is_second_small {
+                    possible.push((
+                        neighbor,
+                        smalls_visited.clone(),
+                        have_second_small || is_second_small,
+                    ))
+                }
+                if is_small_and_new {
+                    smalls_visited.remove(&neighbor);
+                }
+            }
+        }
+
+        Ok((part1, part2))
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+struct CaveId {
+    id: u64,
+}
+
+impl CaveId {
+    // highest bit indicates big cave
+    const BIG_BIT: u64 = i64::MIN as u64;
+    pub fn from_str(s: &str) -> Result<Self> {
+        ensure!(
+            (1..=13).contains(&s.len()),
+            "cave id must have length between 1 and 13 chars"
+        );
+        let is_small = s.as_bytes()[0].is_ascii_lowercase();
+        let alphabet_index = if is_small {
+            u8::to_lowercase_index
+        } else {
+            u8::to_uppercase_index
+        };
+
+        let id = s.bytes().try_fold(0, |id, b| {
+            alphabet_index(b)
+                .context("invalid char in cave id")
+                .map(|b| id * 26 + b as u64)
+        })?;
+        let id = if is_small { id } else { id | Self::BIG_BIT };
+        Ok(Self { id })
+    }
+    pub fn is_small(self) -> bool {
+        0 == self.id & Self::BIG_BIT
+    }
+    pub fn is_big(self) -> bool {
+        !self.is_small()
+    }
+}
+// Unused, but could be useful
+impl fmt::Display for CaveId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut id = self.id;
+        let base = if self.is_small() { b'a' } else { b'A' };
+        if self.is_big() {
+            id &= !Self::BIG_BIT;
+        }
+
+        let mut bytes = [0u8; 13];
+        for b in bytes.iter_mut().rev() {
+            *b = base + (id % 26) as u8;
+            id /= 26;
+            if id == 0 {
+                break;
+            }
+        }
+        for b in bytes.into_iter().skip_while(|&b| b == 0) {
+            use fmt::Write;
+            f.write_char(b as char)?;
+        }
+        Ok(())
+    }
+}
+// Unused, but could be useful
+impl fmt::Debug for CaveId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_fmt(format_args!("{:X} ({})", self.id, self))
+    }
+}
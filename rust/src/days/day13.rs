@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+
+use crate::{numbered_lines, IterTools, ParseError};
+
+const EXAMPLE: &str = r#"6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5
+"#;
+
+crate::day! {
+    Day13 = day 13 -> (usize, String);
+    examples {
+        example: EXAMPLE => (17, r#"█████
+█   █
+█   █
+█   █
+█████"#),
+    }
+    fn solve(input) {
+        let lines = &mut numbered_lines(&input);
+        let mut points: Vec<(u32, u32)> = lines
+            .take_while(|line| !line.text.is_empty())
+            .map(|line| {
+                let (x, y) = line.split_once(line.text, ",")?;
+                Ok((line.parse(x, "a number")?, line.parse(y, "a number")?))
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+        enum Fold {
+            X(u32),
+            Y(u32),
+        }
+        let folds = lines.map(|line| {
+            let fold = line
+                .text
+                .strip_prefix("fold along ")
+                .ok_or_else(|| line.error(line.text, "'fold along '"))?;
+            let (axis, value) = line.split_once(fold, "=")?;
+            let value = line.parse(value, "a number")?;
+            match axis {
+                "x" => Ok(Fold::X(value)),
+                "y" => Ok(Fold::Y(value)),
+                _ => Err(line.error(axis, "x or y")),
+            }
+        });
+
+        let mut part1 = None;
+
+        for fold in folds {
+            let fold = fold?;
+            let do_fold = |p: &mut u32, at: u32| {
+                *p = at
+                    .checked_sub(p.abs_diff(at))
+                    .with_context(|| format!("point {} is too far to fold at {}", p, at))?;
+                Ok::<_, anyhow::Error>(())
+            };
+            match fold {
+                Fold::X(at) => {
+                    for (x, _) in &mut points {
+                        do_fold(x, at)?;
+                    }
+                }
+                Fold::Y(at) => {
+                    for (_, y) in &mut points {
+                        do_fold(y, at)?;
+                    }
+                }
+            }
+            points.sort_unstable();
+            points.dedup();
+            if part1.is_none() {
+                part1 = Some(points.len())
+            }
+        }
+        let part1 = part1.context("failed to find part1")?;
+        // If we got part1, there must have been at least 1 fold.
+        // Therefore, we can assume points are sorted.
+
+        let &(x_min, _) = points.get(0).context("no points left")?;
+        let &(x_max, _) = points.last().unwrap();
+        let (y_min, y_max) = points
+            .iter()
+            .map(|(_, y)| *y)
+            .min_max()
+            .context("less than two points left")?;
+
+        let mut part2 = String::new();
+
+        (y_min..=y_max).for_each(|y| {
+            (x_min..=x_max).for_each(|x| {
+                let c = if points.contains(&(x, y)) { '█' } else { ' ' };
+                part2.push(c);
+            });
+            part2.push('\n');
+        });
+        part2.pop();
+
+        Ok((part1, part2))
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+
+use crate::{numbered_lines, IterTools};
+
+const EXAMPLE: &str = r#"NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C
+"#;
+
+crate::day! {
+    Day14 = day 14 -> (usize, usize);
+    examples {
+        example: EXAMPLE => (1588, 2188189693529),
+    }
+    fn solve(input) {
+        let mut lines = numbered_lines(&input);
+        let init = lines.next().context("missing input")?.text;
+        let mut pair_map = HashMap::new();
+        for line in lines.skip(1) {
+            let (pair, insert) = line.split_once(line.text, " -> ")?;
+            if pair.len() != 2 {
+                return Err(line.error(pair, "a pair of elements").into());
+            }
+            if insert.len() != 1 {
+                return Err(line.error(insert, "a single element").into());
+            }
+            let pair = [pair.as_bytes()[0], pair.as_bytes()[1]];
+            let insert = insert.as_bytes()[0];
+            pair_map.insert(pair, insert);
+        }
+        let mut counts = HashMap::<u8, usize>::new();
+        let init: Vec<_> = init.bytes().collect();
+        for &s in &init {
+            *counts.entry(s).or_default() += 1;
+        }
+        let mut pcounts = HashMap::<[u8; 2], usize>::new();
+        for pair in init.array_windows().copied() {
+            *pcounts.entry(pair).or_default() += 1;
+        }
+        let mut step = 0;
+        let mut after_steps = |n| -> Result<usize> {
+            for _ in 0..n {
+                step += 1;
+                let mut new = HashMap::new();
+                for (pair, count) in &pcounts {
+                    let &insert = pair_map.get(pair).with_context(|| {
+                        format!("no insertion rule for {}", String::from_utf8_lossy(pair))
+                    })?;
+                    *counts.entry(insert).or_default() += count;
+                    *new.entry([pair[0], insert]).or_default() += count;
+                    *new.entry([insert, pair[1]]).or_default() += count;
+                }
+                pcounts = new;
+                crate::trace!(Debug, "step {}: {}", step, render_counts(&pcounts));
+            }
+
+            counts
+                .values()
+                .copied()
+                .min_max()
+                .map(|(min, max)| max - min)
+                .context("no elements")
+        };
+
+        let part1 = after_steps(10).context("part1 steps failed")?;
+        let part2 = after_steps(30).context("part2 steps failed")?;
+
+        Ok((part1, part2))
+    }
+}
+
+/// The pair counts, sorted by pair, as `AB=3 AC=1 ...`.
+fn render_counts(pcounts: &HashMap<[u8; 2], usize>) -> String {
+    let mut counts: Vec<_> = pcounts.iter().collect();
+    counts.sort_unstable();
+    let counts: Vec<String> = counts
+        .into_iter()
+        .map(|(pair, count)| format!("{}{}={}", pair[0] as char, pair[1] as char, count))
+        .collect();
+    counts.join(" ")
+}
@@ -0,0 +1,122 @@
+use anyhow::{Context, Result};
+
+use crate::{numbered_lines, Line, LineReader, ParseError};
+
+const EXAMPLE: &str = r#"forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+"#;
+
+#[test]
+fn test_streaming() {
+    let streamed = <Day2 as crate::StreamingSolution>::solve_reader(EXAMPLE.as_bytes());
+    assert_eq!(streamed.unwrap(), (150, 900));
+}
+
+#[test]
+fn test_overflow() {
+    // Overflows are errors, not panics or wrapped answers.
+    assert!(
+        <Day2 as crate::Solution>::solve("forward 9000000000000\ndown 9000000000000\n".into())
+            .is_err()
+    );
+}
+
+crate::day! {
+    Day2 = day 2 -> (i64, i64);
+    examples {
+        example: EXAMPLE => (150, 900),
+    }
+    type Parsed = Vec<(Direction, i64)>;
+    fn parse(input) {
+        numbered_lines(&input)
+            .map(|line| Ok(parse_command(line)?))
+            .collect()
+    }
+
+    fn part1(commands) {
+        let mut sub = Submarine::default();
+        for &(dir, dist) in commands {
+            sub.move1(dir, dist)?;
+        }
+        sub.product()
+    }
+
+    fn part2(commands) {
+        let mut sub = Submarine::default();
+        for &(dir, dist) in commands {
+            sub.move2(dir, dist)?;
+        }
+        sub.product()
+    }
+
+    fn solve_reader(reader) {
+        let mut lines = LineReader::new(reader);
+        let (mut sub1, mut sub2) = (Submarine::default(), Submarine::default());
+        while let Some(line) = lines.next_line()? {
+            let (dir, dist) = parse_command(line)?;
+            sub1.move1(dir, dist)?;
+            sub2.move2(dir, dist)?;
+        }
+        Ok((sub1.product()?, sub2.product()?))
+    }
+}
+
+fn parse_command(line: Line) -> Result<(Direction, i64), ParseError> {
+    let (dir, dist) = line.split_once(line.text, " ")?;
+    let dist = line.parse::<i64>(dist, "a number")?;
+    let dir = match dir {
+        "forward" => Direction::Forward,
+        "down" => Direction::Down,
+        "up" => Direction::Up,
+        _ => return Err(line.error(dir, "forward, down or up")),
+    };
+    Ok((dir, dist))
+}
+
+#[derive(Default)]
+struct Submarine {
+    hd: i64,
+    vd: i64,
+    aim: i64,
+}
+
+impl Submarine {
+    /// Move as in part 1, where up and down change depth directly.
+    fn move1(&mut self, dir: Direction, dist: i64) -> Result<()> {
+        let moved = match dir {
+            Direction::Forward => self.hd.checked_add(dist).map(|hd| self.hd = hd),
+            Direction::Down => self.vd.checked_add(dist).map(|vd| self.vd = vd),
+            Direction::Up => self.vd.checked_sub(dist).map(|vd| self.vd = vd),
+        };
+        moved.context("position overflowed")
+    }
+    /// Move as in part 2, where up and down change the aim.
+    fn move2(&mut self, dir: Direction, dist: i64) -> Result<()> {
+        let moved = match dir {
+            Direction::Forward => self.hd.checked_add(dist).and_then(|hd| {
+                self.vd = self.aim.checked_mul(dist)?.checked_add(self.vd)?;
+                self.hd = hd;
+                Some(())
+            }),
+            Direction::Down => self.aim.checked_add(dist).map(|aim| self.aim = aim),
+            Direction::Up => self.aim.checked_sub(dist).map(|aim| self.aim = aim),
+        };
+        moved.context("position overflowed")
+    }
+    fn product(&self) -> Result<i64> {
+        self.hd
+            .checked_mul(self.vd)
+            .context("product of positions overflowed")
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Direction {
+    Forward,
+    Down,
+    Up,
+}
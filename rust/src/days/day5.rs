@@ -0,0 +1,90 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{Line, LineReader, ParseError};
+
+const EXAMPLE: &str = r#"0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+"#;
+
+#[test]
+fn test_streaming() {
+    let streamed = <Day5 as crate::StreamingSolution>::solve_reader(EXAMPLE.as_bytes());
+    assert_eq!(streamed.unwrap(), (5, 12));
+}
+
+crate::day! {
+    Day5 = day 5 -> (usize, usize);
+    examples {
+        example: EXAMPLE => (5, 12),
+    }
+    /// Memory is bounded by the number of points covered, not the length of the input.
+    fn solve_reader(reader) {
+        let mut lines = LineReader::new(reader);
+        let parse_line = |line: Line| {
+            let point = |part| {
+                let (x, y) = line.split_once(part, ",")?;
+                Ok::<_, ParseError>((line.parse(x, "a number")?, line.parse(y, "a number")?))
+            };
+            let (start, end) = line.split_once(line.text, " -> ")?;
+            let (x1, y1): (u16, u16) = point(start)?;
+            let (x2, y2): (u16, u16) = point(end)?;
+            Ok::<_, ParseError>([(x1, y1), (x2, y2)])
+        };
+
+        #[derive(Copy, Clone, Default)]
+        struct Count {
+            hv: usize,
+            d: usize,
+        }
+
+        let mut points = HashMap::<_, Count>::new();
+
+        while let Some(line) = lines.next_line()? {
+            let [(x1, y1), (x2, y2)] = parse_line(line)?;
+
+            match (x1.cmp(&x2), y1.cmp(&y2)) {
+                (Ordering::Equal, _) => {
+                    let x = x1;
+                    let (y1, y2) = (y1.min(y2), y1.max(y2));
+                    (y1..=y2).for_each(|y| points.entry((x, y)).or_default().hv += 1)
+                }
+                (_, Ordering::Equal) => {
+                    let y = y1;
+                    let (x1, x2) = (x1.min(x2), x1.max(x2));
+                    (x1..=x2).for_each(|x| points.entry((x, y)).or_default().hv += 1)
+                }
+                (dx, dy) if dx == dy => {
+                    let (x1, x2) = (x1.min(x2), x1.max(x2));
+                    let (y1, y2) = (y1.min(y2), y1.max(y2));
+                    (x1..=x2)
+                        .zip(y1..=y2)
+                        .for_each(|(x, y)| points.entry((x, y)).or_default().d += 1)
+                }
+                _ => {
+                    let (x1, x2) = (x1.min(x2), x1.max(x2));
+                    let (y1, y2) = (y1.min(y2), y1.max(y2));
+                    (x1..=x2)
+                        .rev()
+                        .zip(y1..=y2)
+                        .for_each(|(x, y)| points.entry((x, y)).or_default().d += 1)
+                }
+            }
+        }
+
+        let (part1, part2) = points.values().fold((0, 0), |(p1, p2), &count| {
+            let p1 = p1 + (count.hv > 1) as usize;
+            let p2 = p2 + (count.hv + count.d > 1) as usize;
+            (p1, p2)
+        });
+
+        Ok((part1, part2))
+    }
+}
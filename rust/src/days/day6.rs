@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use anyhow::Context;
+
+use crate::numbered_lines;
+
+crate::day! {
+    Day6 = day 6 -> (u64, u64), budget Duration::from_millis(1);
+    examples {
+        example: "3,4,3,1,2" => (5934, 26984457539),
+    }
+    fn solve(input) {
+        let mut timers = [0u64; 9];
+
+        let line = numbered_lines(&input).next().context("missing input")?;
+        for s in line.text.trim().split(',') {
+            let timer = line.parse::<u8>(s, "a timer")?;
+            *timers
+                .get_mut(timer as usize)
+                .ok_or_else(|| line.error(s, "a timer from 0 to 8"))? += 1;
+        }
+
+        fn step7(timers: [u64; 9]) -> [u64; 9] {
+            // 0 -> 0,2
+            // 1 -> 1,3
+            // 2 -> 2,4
+            // 3 -> 3,5
+            // 4 -> 4,6
+            // 5 -> 5,7
+            // 6 -> 6,8
+            // 7 -> 0
+            // 8 -> 1
+            [
+                timers[0] + timers[7],
+                timers[1] + timers[8],
+                timers[2] + timers[0],
+                timers[3] + timers[1],
+                timers[4] + timers[2],
+                timers[5] + timers[3],
+                timers[6] + timers[4],
+                timers[5],
+                timers[6],
+            ]
+        }
+        fn step1(timers: [u64; 9]) -> [u64; 9] {
+            [
+                timers[1],
+                timers[2],
+                timers[3],
+                timers[4],
+                timers[5],
+                timers[6],
+                timers[7] + timers[0],
+                timers[8],
+                timers[0],
+            ]
+        }
+
+        let sevens = 80 / 7;
+        let ones = 80 % 7;
+        for _ in 0..sevens {
+            timers = step7(timers)
+        }
+        for _ in 0..ones {
+            timers = step1(timers)
+        }
+        let part1 = timers.iter().copied().sum();
+
+        let sevens = (256 - 80) / 7;
+        let ones = (256 - 80) % 7;
+        for _ in 0..sevens {
+            timers = step7(timers)
+        }
+        for _ in 0..ones {
+            timers = step1(timers)
+        }
+        let part2 = timers.iter().copied().sum();
+
+        Ok((part1, part2))
+    }
+}
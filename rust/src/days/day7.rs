@@ -0,0 +1,79 @@
+use anyhow::{Context, Result};
+
+use crate::numbered_lines;
+
+#[test]
+fn test_distant_crabs() {
+    // Far apart crabs are solved quickly, and overflows are errors.
+    let (part1, _) = <Day7 as crate::Solution>::solve("0,4294967295,4294967295".into()).unwrap();
+    assert_eq!(part1, 4294967295);
+    let overflow = "0,4294967295,".repeat(5);
+    assert!(<Day7 as crate::Solution>::solve(overflow.trim_end_matches(',').into()).is_err());
+}
+
+crate::day! {
+    Day7 = day 7 -> (u64, u64);
+    examples {
+        example: "16,1,2,0,4,2,7,1,2,14" => (37, 168),
+    }
+    /// Crab positions, with the min and max position
+    type Parsed = (Vec<u32>, u32, u32);
+    fn parse(input) {
+        let mut min = u32::MAX;
+        let mut max = 0;
+        let line = numbered_lines(&input).next().context("missing input")?;
+        let positions: Vec<u32> = line
+            .text
+            .trim()
+            .split(',')
+            .map(|n| line.parse(n, "a number").map_err(Into::into))
+            .try_fold::<_, _, Result<_>>(vec![], |mut vec, p: Result<u32>| {
+                let p = p?;
+                min = p.min(min);
+                max = p.max(max);
+                vec.push(p);
+                Ok(vec)
+            })?;
+        Ok((positions, min, max))
+    }
+
+    fn part1(crabs) {
+        best_fuel(crabs, |a, b| a.abs_diff(b) as u64)
+    }
+
+    fn part2(crabs) {
+        let p2_fuel_cost = |a: u32, b: u32| {
+            let distance = a.abs_diff(b) as u64;
+            distance * (distance + 1) / 2
+        };
+        best_fuel(crabs, p2_fuel_cost)
+    }
+}
+
+/// The least total fuel to align at any position between `min` and `max`.
+///
+/// The total is convex in the position, so a binary search for where it stops falling
+/// finds the least, however far apart the crabs are.
+fn best_fuel(
+    (positions, min, max): &(Vec<u32>, u32, u32),
+    cost_fn: fn(u32, u32) -> u64,
+) -> Result<u64> {
+    let fuel = |p| {
+        positions
+            .iter()
+            .map(|&n| cost_fn(n, p) as u128)
+            .sum::<u128>()
+    };
+    let (mut lo, mut hi) = (*min, *max);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if fuel(mid) <= fuel(mid + 1) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    u64::try_from(fuel(lo))
+        .ok()
+        .context("total fuel overflowed")
+}
@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+
+use crate::{numbered_lines, Line, ParseError};
+
+const EXAMPLE: &str = r#"be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce"#;
+
+#[test]
+fn test_overflow() {
+    // Twenty 8s are too many digits for a usize.
+    let line = format!(
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb |{}\n",
+        " cfbegad".repeat(20)
+    );
+    assert!(<Day8 as crate::Solution>::solve(line).is_err());
+}
+
+crate::day! {
+    Day8 = day 8 -> (usize, usize);
+    examples {
+        example: EXAMPLE => (26, 61229),
+    }
+    fn solve(input) {
+        fn bits_from_segments(line: Line, segments: &str) -> Result<u8, ParseError> {
+            segments.bytes().try_fold(0, |bits, segment| match segment {
+                b'a'..=b'g' => Ok(bits | (1 << (segment - b'a'))),
+                _ => Err(line.error(segments, "segments a to g")),
+            })
+        }
+        let input: Vec<(Vec<u8>, Vec<u8>)> = numbered_lines(&input)
+            .map(|line| {
+                let (pattern, code) = line.split_once(line.text, "|")?;
+                let pattern: Vec<u8> = pattern
+                    .split_ascii_whitespace()
+                    .map(|segments| bits_from_segments(line, segments))
+                    .collect::<Result<_, _>>()?;
+                let code: Vec<u8> = code
+                    .split_ascii_whitespace()
+                    .map(|segments| bits_from_segments(line, segments))
+                    .collect::<Result<_, _>>()?;
+                Ok((pattern, code))
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        fn solve_pattern(pattern: &[u8]) -> Option<[u8; 10]> {
+            let &one = pattern.iter().find(|&&p| p.count_ones() == 2)?;
+            let &seven = pattern.iter().find(|&&p| p.count_ones() == 3)?;
+            let &four = pattern.iter().find(|&&p| p.count_ones() == 4)?;
+            let &eight = pattern.iter().find(|&&p| p.count_ones() == 7)?;
+            let &three = pattern
+                .iter()
+                .find(|&&p| p.count_ones() == 5 && (p & one).count_ones() == 2)?;
+            let &two = pattern
+                .iter()
+                .find(|&&p| p.count_ones() == 5 && (p & four).count_ones() == 2)?;
+            let &five = pattern
+                .iter()
+                .find(|&&p| p != three && p.count_ones() == 5 && (p & four).count_ones() == 3)?;
+            let &six = pattern
+                .iter()
+                .find(|&&p| p.count_ones() == 6 && (p & one).count_ones() == 1)?;
+            let &zero = pattern
+                .iter()
+                .find(|&&p| p != six && p.count_ones() == 6 && (p & five).count_ones() == 4)?;
+            let &nine = pattern
+                .iter()
+                .find(|&&p| p != zero && p != six && p.count_ones() == 6)?;
+
+            Some([zero, one, two, three, four, five, six, seven, eight, nine])
+        }
+
+        let mut part1 = 0;
+        let mut part2 = 0;
+
+        for (pattern, code) in input {
+            let pattern = solve_pattern(&pattern).context("bad pattern")?;
+            let mut digits: usize = 0;
+            for bits in code {
+                let digit = pattern
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, &p)| (bits == p).then(|| i))
+                    .next()
+                    .context("failed to match digit")?;
+                if digit == 1 || digit == 4 || digit == 7 || digit == 8 {
+                    part1 += 1;
+                }
+                digits = digits
+                    .checked_mul(10)
+                    .and_then(|digits| digits.checked_add(digit))
+                    .context("output value overflowed")?;
+            }
+            part2 = digits
+                .checked_add(part2)
+                .context("sum of output values overflowed")?;
+        }
+
+        Ok((part1, part2))
+    }
+}
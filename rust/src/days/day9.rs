@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use anyhow::Context;
+
+use crate::{numbered_lines, Ascii, Grid, GridIndex, GridSize, Line, Neighbors::Cardinal};
+
+const EXAMPLE: &str = r#"2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+"#;
+
+crate::day! {
+    Day9 = day 9 -> (usize, usize);
+    examples {
+        example: EXAMPLE => (15, 1134),
+    }
+    fn solve(input) {
+        let lines: Vec<Line> = numbered_lines(&input).collect();
+        let width = lines.get(0).context("missing input")?.text.len();
+        let height = lines.len();
+
+        let mut input = Vec::with_capacity(width * height);
+        for line in lines {
+            for (i, c) in line.text.char_indices() {
+                let digit = (c as u32).try_into().ok().and_then(u8::to_digit);
+                input.push(digit.ok_or_else(|| line.error(&line.text[i..], "a digit"))?);
+            }
+        }
+        let input = Grid::new(input, GridSize { width, height })?;
+
+        let is_low_point = |index: GridIndex| {
+            let this = input[index];
+            index
+                .neighbors_iter(Cardinal)
+                .all(|neighbor| input[neighbor] > this)
+        };
+
+        let basin_size = |low_point: GridIndex| {
+            let mut basin = HashSet::new();
+            basin.insert(low_point.index);
+            let mut filter =
+                |&neighbor: &GridIndex| input[neighbor] != 9 && basin.insert(neighbor.index);
+            let mut neighbors: Vec<_> = low_point
+                .neighbors_iter(Cardinal)
+                .filter(&mut filter)
+                .collect();
+            let mut size = neighbors.len() + 1;
+            while let Some(neighbor) = neighbors.pop() {
+                neighbors.extend(
+                    neighbor
+                        .neighbors_iter(Cardinal)
+                        .filter(&mut filter)
+                        .inspect(|_| size += 1),
+                )
+            }
+            size
+        };
+
+        let mut basins = vec![];
+        let part1 = (0..input.len())
+            .map(|index| GridIndex {
+                index,
+                size: input.size(),
+            })
+            .filter_map(|index| {
+                is_low_point(index).then(|| {
+                    basins.push(basin_size(index));
+                    input[index] as usize + 1
+                })
+            })
+            .sum();
+        basins.sort_unstable();
+        let part2 = basins.into_iter().rev().take(3).product();
+
+        Ok((part1, part2))
+    }
+}
@@ -0,0 +1,324 @@
+use std::{
+    fmt,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{bail, ensure, Context, Result};
+
+use crate::{Example, Options, Record, Solution, StreamingSolution};
+
+mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+
+pub use self::{
+    day1::Day1, day10::Day10, day11::Day11, day12::Day12, day13::Day13, day14::Day14, day2::Day2,
+    day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8, day9::Day9,
+};
+
+/// A type-erased [`Solution`], so that days can be listed and run by number.
+#[derive(Copy, Clone)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    /// See [`Solution::BUDGET`].
+    pub budget: Option<Duration>,
+    solve: fn(String) -> Result<(String, String)>,
+    record: fn(&Options) -> Record,
+    examples: fn() -> Vec<Example<String, String>>,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Self {
+        fn solve<S: Solution>(input: String) -> Result<(String, String)> {
+            let (part1, part2) = S::solve(input)?;
+            Ok((part1.to_string(), part2.to_string()))
+        }
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            budget: S::BUDGET,
+            solve: solve::<S>,
+            record: crate::record_solution::<S>,
+            examples: S::examples,
+        }
+    }
+    /// Like [`of`](Self::of), but the day can stream its input, see
+    /// [`record_streaming_solution`](crate::record_streaming_solution).
+    pub fn streaming<S: StreamingSolution>() -> Self {
+        Self {
+            record: crate::record_streaming_solution::<S>,
+            ..Self::of::<S>()
+        }
+    }
+    /// Solve both parts, formatting the answers as they would be printed.
+    pub fn solve(&self, input: String) -> Result<(String, String)> {
+        (self.solve)(input)
+    }
+    /// Run as configured by `options`, see [`record_solution`](crate::record_solution).
+    pub fn record(&self, options: &Options) -> Record {
+        (self.record)(options)
+    }
+    /// The examples in the day's source, see [`Solution::examples`].
+    pub fn examples(&self) -> Vec<Example<String, String>> {
+        (self.examples)()
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Day")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("budget", &self.budget)
+            .finish()
+    }
+}
+
+/// Every implemented day, in order of year and day.
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::streaming::<Day1>(),
+        Day::streaming::<Day2>(),
+        Day::of::<Day3>(),
+        Day::of::<Day4>(),
+        Day::streaming::<Day5>(),
+        Day::of::<Day6>(),
+        Day::of::<Day7>(),
+        Day::of::<Day8>(),
+        Day::of::<Day9>(),
+        Day::streaming::<Day10>(),
+        Day::of::<Day11>(),
+        Day::of::<Day12>(),
+        Day::of::<Day13>(),
+        Day::of::<Day14>(),
+    ]
+}
+
+/// Every implemented day of the given year, in order.
+pub fn of_year(year: u16) -> Vec<Day> {
+    all().into_iter().filter(|d| d.year == year).collect()
+}
+
+/// Find the implemented day with the given year and number.
+pub fn get(year: u16, day: u8) -> Option<Day> {
+    all().into_iter().find(|d| d.year == year && d.day == day)
+}
+
+/// The number of days to solve at once by default: one per CPU.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, usize::from)
+}
+
+/// How many runs, one after another, decide whether a day meets its budget.
+pub const BUDGET_RUNS: usize = 5;
+
+/// Re-run each day whose record is over its budget [`BUDGET_RUNS`] times, one at a time,
+/// and keep its fastest record, so that no day misses its budget only because other days
+/// or programs were running at the same time. `records` are those of `runs`, in order.
+pub fn retime_over_budget(runs: &[(Day, Options)], records: &mut [Record]) {
+    for ((day, options), record) in runs.iter().zip(records) {
+        if !record.over_budget() || record.error.is_some() {
+            continue;
+        }
+        for _ in 0..BUDGET_RUNS {
+            let rerun = day.record(options);
+            let total = |record: &Record| record.timings.unwrap_or_default().total;
+            if rerun.error.is_none() && total(&rerun) < total(record) {
+                *record = rerun;
+            }
+        }
+    }
+}
+
+/// Run every day as configured by `options`, on up to `jobs` threads at once.
+///
+/// The records are returned in the same order as `days`; a day which panics gets a record
+/// with an error. Each day is timed on its own thread, so timings are only comparable
+/// with those of a sequential run (`jobs == 1`) when there are enough idle cores.
+pub fn record_all(days: &[Day], options: &Options, jobs: usize) -> Vec<Record> {
+    let runs: Vec<(Day, Options)> = days.iter().map(|&day| (day, options.clone())).collect();
+    record_each(&runs, jobs)
+}
+
+/// Like [`record_all`], but with separate options for each day, such as to run the same
+/// day with several profiles.
+pub fn record_each(runs: &[(Day, Options)], jobs: usize) -> Vec<Record> {
+    let jobs = jobs.clamp(1, runs.len().max(1));
+    if jobs == 1 {
+        return runs
+            .iter()
+            .map(|(day, options)| record_caught(day, options))
+            .collect();
+    }
+    let runs: Arc<[(Day, Options)]> = runs.into();
+    let next = Arc::new(AtomicUsize::new(0));
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..jobs {
+        let (runs, next, sender) = (runs.clone(), next.clone(), sender.clone());
+        thread::spawn(move || loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            let (day, options) = match runs.get(i) {
+                Some(run) => run,
+                None => break,
+            };
+            if sender.send((i, record_caught(day, options))).is_err() {
+                break;
+            }
+        });
+    }
+    drop(sender);
+    let mut records: Vec<(usize, Record)> = receiver.into_iter().collect();
+    records.sort_unstable_by_key(|&(i, _)| i);
+    records.into_iter().map(|(_, record)| record).collect()
+}
+
+fn record_caught(day: &Day, options: &Options) -> Record {
+    panic::catch_unwind(AssertUnwindSafe(|| day.record(options))).unwrap_or_else(|payload| Record {
+        error: Some(format!("panicked: {}", crate::panic_message(&*payload))),
+        ..Record::new(day.year, day.day)
+    })
+}
+
+/// Parse a selection of days of the given year, such as `7`, `3..=9`, `3..10` or `1,4,7`.
+///
+/// The days are returned sorted and without duplicates. Days which are not implemented
+/// are an error.
+pub fn parse_selection(year: u16, selection: &str) -> Result<Vec<Day>> {
+    let parse_day = |s: &str| {
+        s.trim()
+            .parse::<u8>()
+            .with_context(|| format!("invalid day '{}'", s))
+    };
+    let mut days = vec![];
+    for item in selection.split(',').filter(|s| !s.trim().is_empty()) {
+        let range: RangeInclusive<u8> = if let Some((start, end)) = item.split_once("..=") {
+            parse_day(start)?..=parse_day(end)?
+        } else if let Some((start, end)) = item.split_once("..") {
+            let end = parse_day(end)?;
+            ensure!(end > 0, "invalid day range '{}'", item);
+            parse_day(start)?..=end - 1
+        } else {
+            let day = parse_day(item)?;
+            day..=day
+        };
+        ensure!(!range.is_empty(), "empty day range '{}'", item);
+        days.extend(range);
+    }
+    days.sort_unstable();
+    days.dedup();
+    days.into_iter()
+        .map(|day| match get(year, day) {
+            Some(day) => Ok(day),
+            None => bail!("{} day {} is not implemented", year, day),
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_selection() {
+    let days = |s| -> Vec<u8> {
+        parse_selection(2021, s)
+            .unwrap()
+            .iter()
+            .map(|d| d.day)
+            .collect()
+    };
+    assert_eq!(days("7"), [7]);
+    assert_eq!(days("3..=5"), [3, 4, 5]);
+    assert_eq!(days("3..5"), [3, 4]);
+    assert_eq!(days("9,1,4..=5,4"), [1, 4, 5, 9]);
+    assert!(parse_selection(2021, "5..=3").is_err());
+    assert!(parse_selection(2021, "0").is_err());
+    assert!(parse_selection(2021, "x").is_err());
+    assert!(parse_selection(2015, "1").is_err());
+}
+
+#[test]
+fn test_record_each() {
+    // Every run fails to read its input, quickly, but still gets its own record.
+    let options = Options {
+        input: crate::InputSource::File("no/such/input.txt".into()),
+        ..Options::default()
+    };
+    let runs: Vec<(Day, Options)> = all()
+        .into_iter()
+        .rev()
+        .map(|day| (day, options.clone()))
+        .collect();
+    let records = record_each(&runs, 4);
+    let days: Vec<u8> = records.iter().map(|r| r.day).collect();
+    let expected: Vec<u8> = runs.iter().map(|(day, _)| day.day).collect();
+    assert_eq!(days, expected);
+    assert!(records.iter().all(|r| r.error.is_some()));
+}
+
+#[test]
+fn test_record_streaming() {
+    let dir = crate::test_dir("stream");
+    let path = dir.join("input.txt");
+    std::fs::write(&path, "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n").unwrap();
+    let options = Options {
+        input: crate::InputSource::File(path),
+        stream: true,
+        part: Some(2),
+        ..Options::default()
+    };
+    let record = get(2021, 1).unwrap().record(&options);
+    assert_eq!(record.error, None);
+    assert_eq!((record.part1, record.part2), (None, Some("5".into())));
+    let error = get(2021, 3).unwrap().record(&options).error.unwrap();
+    assert!(error.contains("cannot stream"), "{}", error);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_budgets() {
+    // Budgets are for the real input, so only days with a cached input are checked.
+    let cache = crate::InputCache::from_env();
+    let runs: Vec<(Day, Options)> = all()
+        .into_iter()
+        .filter(|day| day.budget.is_some())
+        .filter(|day| matches!(cache.load(day.year, day.day), Ok(Some(_))))
+        .map(|day| (day, Options::default()))
+        .collect();
+    // Other tests run at the same time, so only the fastest of a few runs counts.
+    let mut records = record_each(&runs, 1);
+    retime_over_budget(&runs, &mut records);
+    let over: Vec<String> = records
+        .into_iter()
+        .filter(Record::over_budget)
+        .map(|record| {
+            format!(
+                "{} day {:02} took {:.2?}, over its budget of {:.2?}",
+                record.year,
+                record.day,
+                record.timings.unwrap_or_default().total,
+                record.budget.unwrap_or_default()
+            )
+        })
+        .collect();
+    if cfg!(debug_assertions) {
+        over.iter().for_each(|over| eprintln!("WARN: {}", over));
+    } else {
+        assert!(over.is_empty(), "{}", over.join("\n"));
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    fmt, iter,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::days::Day;
+
+/// A small xorshift generator, so that a failing run can be reproduced from its seed.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck at zero
+        Self(seed ^ 0x9e37_79b9_7f4a_7c15)
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+    /// A number in `0..n`; `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// Characters mixed in with the seed's own: control and non-ASCII characters which are
+/// never in a valid input, and separators and digits in places they do not belong.
+const HOSTILE: &[char] = &[
+    '\0', '\r', '\t', 'é', '█', '-', ',', ' ', '\n', '9', 'z', 'Z',
+];
+
+/// The most characters in a mutated input.
+const MAX_LEN: usize = 1 << 16;
+
+/// Numbers at the edges of the integer types, to replace the seed's own numbers.
+const HUGE: &[&str] = &[
+    "2147483648",
+    "4294967295",
+    "4294967296",
+    "9223372036854775807",
+    "-9223372036854775808",
+    "18446744073709551615",
+    "18446744073709551616",
+    "340282366920938463463374607431768211456",
+];
+
+/// Apply a few random edits to `seed`: changing, inserting and removing characters,
+/// duplicating, removing, swapping and truncating lines, and making it larger than any
+/// real input with huge numbers, long lines and long runs of one line or token.
+///
+/// Inserted characters are sometimes any character at all, not only ASCII. The result is
+/// cut to 65536 characters, so repeated growth stays quick to solve.
+pub fn mutate(rng: &mut Rng, seed: &str) -> String {
+    let mut alphabet: Vec<char> = seed.chars().collect();
+    alphabet.extend_from_slice(HOSTILE);
+    let mut chars: Vec<char> = seed.chars().collect();
+    for _ in 0..1 + rng.below(4) {
+        let len = chars.len();
+        match rng.below(12) {
+            0 if len > 0 => {
+                let i = rng.below(len);
+                chars[i] = random_char(rng, &alphabet);
+            }
+            1 => {
+                let i = rng.below(len + 1);
+                chars.insert(i, random_char(rng, &alphabet));
+            }
+            2 if len > 0 => {
+                chars.remove(rng.below(len));
+            }
+            3 if len > 0 => chars.truncate(rng.below(len)),
+            4 => {
+                // Replace the number at a random place, or insert one.
+                let mut i = rng.below(len + 1);
+                while i > 0 && chars[i - 1].is_ascii_digit() {
+                    i -= 1;
+                }
+                let mut j = i;
+                while j < len && chars[j].is_ascii_digit() {
+                    j += 1;
+                }
+                chars.splice(i..j, rng.pick(HUGE).chars());
+            }
+            5 if len > 0 => {
+                // Repeat a short token in place, such as a bracket or a digit.
+                let i = rng.below(len);
+                let j = (i + 1 + rng.below(4)).min(len);
+                let token: Vec<char> = chars[i..j].to_vec();
+                let copies = 2 + rng.below(200);
+                let run: Vec<char> = token
+                    .iter()
+                    .copied()
+                    .cycle()
+                    .take(token.len() * copies)
+                    .collect();
+                chars.splice(i..j, run);
+            }
+            _ => {
+                let text: String = chars.iter().collect();
+                let mut lines: Vec<String> = text.split('\n').map(String::from).collect();
+                let (i, j) = (rng.below(lines.len()), rng.below(lines.len()));
+                match rng.below(5) {
+                    0 => lines.insert(j, lines[i].clone()),
+                    1 if lines.len() > 1 => {
+                        lines.remove(i);
+                    }
+                    2 => {
+                        // Many copies of one line.
+                        let copies = 2 + rng.below(1000);
+                        let line = lines[i].clone();
+                        lines.splice(i..i, iter::repeat(line).take(copies));
+                    }
+                    3 => lines[i] = lines[i].repeat(2 + rng.below(100)),
+                    _ => lines.swap(i, j),
+                }
+                chars = lines.join("\n").chars().collect();
+            }
+        }
+        chars.truncate(MAX_LEN);
+    }
+    chars.into_iter().collect()
+}
+
+/// Usually a character from `alphabet`, otherwise any byte value or any character.
+fn random_char(rng: &mut Rng, alphabet: &[char]) -> char {
+    match rng.below(4) {
+        0 => char::from(rng.below(256) as u8),
+        1 => char::from_u32(rng.below(0x11_0000) as u32).unwrap_or(char::REPLACEMENT_CHARACTER),
+        _ => *rng.pick(alphabet),
+    }
+}
+
+/// A solve which panicked.
+#[derive(Clone, Debug)]
+pub struct FuzzFailure {
+    pub day: u8,
+    pub input: String,
+    pub message: String,
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {:02} panicked: {}\ninput: {:?}",
+            self.day, self.message, self.input
+        )
+    }
+}
+
+/// Solve `runs` mutations of the seed inputs, and return the first which panics.
+///
+/// Errors are fine; any input which panics is a bug in the solution.
+pub fn fuzz(day: &Day, seeds: &[&str], runs: usize, rng: &mut Rng) -> Result<(), FuzzFailure> {
+    let inputs = seeds
+        .iter()
+        .map(|&seed| seed.to_owned())
+        .chain((0..runs).map(|_| {
+            let seed = *rng.pick(seeds);
+            mutate(rng, seed)
+        }));
+    for input in inputs {
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| day.solve(input.clone()))) {
+            return Err(FuzzFailure {
+                day: day.day,
+                input,
+                message: crate::panic_message(&*payload),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A small, valid input for each day, to mutate.
+#[cfg(test)]
+const SEEDS: [&str; 14] = [
+    "199\n200\n208\n210\n200\n207\n",
+    "forward 5\ndown 5\nforward 8\nup 3\n",
+    "00100\n11110\n10110\n10111\n",
+    "7,4,9\n\n1 2 3 4 5\n6 7 8 9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25\n",
+    "0,9 -> 5,9\n8,0 -> 0,8\n9,4 -> 3,4\n",
+    "3,4,3,1,2\n",
+    "16,1,2,0,4,2,7,1,2,14\n",
+    "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe\n",
+    "2199943210\n3987894921\n9856789892\n",
+    "[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(\n",
+    "11111\n19991\n19191\n19991\n11111\n",
+    "start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end\n",
+    "6,10\n0,14\n9,10\n\nfold along y=7\nfold along x=5\n",
+    "NNCB\n\nCH -> B\nHH -> N\nCB -> H\nNH -> C\n",
+];
+
+#[test]
+fn test_fuzz_all_days() {
+    let env = |name, default| {
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    let runs = env("AOC_FUZZ_RUNS", 500) as usize;
+    let mut rng = Rng::new(env("AOC_FUZZ_SEED", 2021));
+    let failures: Vec<String> = crate::days::of_year(2021)
+        .iter()
+        .filter_map(|day| {
+            // A newly scaffolded day has no seed until one is added.
+            let &seed = SEEDS.get(day.day as usize - 1)?;
+            fuzz(day, &[seed], runs, &mut rng).err()
+        })
+        .map(|failure| failure.to_string())
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
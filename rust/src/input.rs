@@ -0,0 +1,267 @@
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Once,
+};
+
+use anyhow::{ensure, Context, Result};
+
+/// The site used when `AOC_BASE_URL` is not set.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The environment variable holding the session cookie of the default profile.
+pub(crate) const SESSION_COOKIE_VAR: &str = "AOC_SESSION_COOKIE";
+
+/// The directory used for cached inputs when `AOC_INPUT_DIR` is not set.
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+
+/// An on-disk cache of puzzle inputs and description pages.
+///
+/// Inputs are stored as `{dir}/YYYY/dayNN.txt`, and pages as `{dir}/YYYY/dayNN.html`.
+#[derive(Clone, Debug)]
+pub struct InputCache {
+    dir: PathBuf,
+    refresh: bool,
+    cookie_var: String,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            refresh: false,
+            cookie_var: SESSION_COOKIE_VAR.to_owned(),
+        }
+    }
+    /// Configure the cache from the run-time environment, including any `.env` file.
+    ///
+    /// `AOC_INPUT_DIR` overrides the cache directory, and setting `AOC_REFRESH_INPUT`
+    /// to anything other than `0` or an empty string forces a fresh download.
+    pub fn from_env() -> Self {
+        load_dotenv();
+        Self::from_vars(
+            std::env::var_os("AOC_INPUT_DIR"),
+            std::env::var("AOC_REFRESH_INPUT").ok(),
+        )
+    }
+    fn from_vars(dir: Option<OsString>, refresh: Option<String>) -> Self {
+        let dir = dir
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR));
+        let refresh = refresh.map_or(false, |v| !v.is_empty() && v != "0");
+        Self::new(dir).with_refresh(refresh)
+    }
+    /// When set, cached inputs are ignored and overwritten by a fresh download.
+    pub fn with_refresh(self, refresh: bool) -> Self {
+        Self { refresh, ..self }
+    }
+    /// Download with the session cookie in the environment variable `var`, instead of
+    /// `AOC_SESSION_COOKIE`.
+    pub fn with_cookie_var(self, var: impl Into<String>) -> Self {
+        Self {
+            cookie_var: var.into(),
+            ..self
+        }
+    }
+    pub fn refresh(&self) -> bool {
+        self.refresh
+    }
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+    /// The path of a hand-saved example input, which is never downloaded.
+    pub fn example_path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{:02}.example.txt", day))
+    }
+    /// The path of the cached description page.
+    pub fn puzzle_path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{:02}.html", day))
+    }
+    /// Read the cached input for the given day, if there is one.
+    pub fn load(&self, year: u16, day: u8) -> Result<Option<String>> {
+        read_cached(&self.path(year, day))
+    }
+    /// Write the input for the given day to the cache.
+    pub fn store(&self, year: u16, day: u8, data: &str) -> Result<()> {
+        write_cached(&self.path(year, day), data)
+    }
+    /// Get the input for the given day from the cache, downloading it if needed.
+    ///
+    /// Only successfully downloaded, plausible inputs are written to the cache.
+    pub fn get(&self, year: u16, day: u8) -> Result<String> {
+        if !self.refresh {
+            if let Some(data) = self.load(year, day)? {
+                return Ok(data);
+            }
+        }
+        let cookie = session_cookie_from(&self.cookie_var)?;
+        let data = download_input_with(&cookie, year, day)?;
+        self.store(year, day, &data)?;
+        Ok(data)
+    }
+    /// Get the description page for the given day from the cache, downloading it with
+    /// the session cookie if needed, or without it if it is not set.
+    ///
+    /// Part 2 is only on pages downloaded after part 1 was solved; see [`Self::forget_puzzle`].
+    pub fn get_puzzle(&self, year: u16, day: u8) -> Result<String> {
+        let path = self.puzzle_path(year, day);
+        if !self.refresh {
+            if let Some(html) = read_cached(&path)? {
+                return Ok(html);
+            }
+        }
+        let cookie = session_cookie_from(&self.cookie_var).ok();
+        let html = crate::puzzle::download_puzzle_with(cookie.as_deref(), year, day)?;
+        ensure!(
+            html.contains("<article"),
+            "downloaded page has no puzzle description"
+        );
+        write_cached(&path, &html)?;
+        Ok(html)
+    }
+    /// Remove the cached description page, so that the next [`Self::get_puzzle`]
+    /// downloads it again. Done after a correct answer, which changes the page.
+    pub fn forget_puzzle(&self, year: u16, day: u8) -> Result<()> {
+        let path = self.puzzle_path(year, day);
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn read_cached(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+fn write_cached(path: &Path, data: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(path, data).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Load the `.env` file, if any, into the environment.
+///
+/// Only the first call has any effect.
+pub(crate) fn load_dotenv() {
+    static LOAD: Once = Once::new();
+    LOAD.call_once(|| {
+        if dotenv::dotenv().is_err() {
+            eprintln!("WARN: failed to load .env file")
+        }
+    });
+}
+
+/// The base url of the Advent of Code site.
+///
+/// May be overridden with the run-time environment variable `AOC_BASE_URL`,
+/// for example to point at a local stand-in server.
+pub(crate) fn base_url() -> String {
+    std::env::var("AOC_BASE_URL")
+        .map(|url| url.trim_end_matches('/').to_owned())
+        .unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned())
+}
+
+/// The `Cookie` header value for requests to the Advent of Code site.
+pub(crate) fn session_cookie() -> Result<String> {
+    session_cookie_from(SESSION_COOKIE_VAR)
+}
+
+/// The `Cookie` header value, from the session cookie in the environment variable `var`.
+pub(crate) fn session_cookie_from(var: &str) -> Result<String> {
+    let session_cookie_value =
+        std::env::var(var).with_context(|| format!("failed to read {} from environment", var))?;
+    Ok(format!("session={}", session_cookie_value))
+}
+
+/// Download the real input for the given day, bypassing the cache.
+///
+/// Requires a run-time environment variable named `AOC_SESSION_COOKIE`.
+/// If this variable is not set, the function will fail.
+pub fn download_input(year: u16, day: u8) -> Result<String> {
+    download_input_with(&session_cookie()?, year, day)
+}
+
+/// Download the real input for the given day, with `cookie` as the `Cookie` header.
+pub fn download_input_with(cookie: &str, year: u16, day: u8) -> Result<String> {
+    let url = format!("{}/{}/day/{}/input", base_url(), year, day);
+    let data = ureq::get(&url)
+        .set("Cookie", cookie)
+        .call()
+        .context("http request error")?
+        .into_string()
+        .context("http response error")?;
+    validate_input(&data)?;
+    Ok(data)
+}
+
+/// Reject responses which cannot be a puzzle input, such as a login page.
+fn validate_input(data: &str) -> Result<()> {
+    ensure!(!data.trim().is_empty(), "downloaded input is empty");
+    // Some real inputs start with '<' (day 10), so look for an actual html tag.
+    let start: String = data.trim_start().chars().take(9).collect();
+    let start = start.to_ascii_lowercase();
+    ensure!(
+        !start.starts_with("<!doctype") && !start.starts_with("<html"),
+        "downloaded input looks like an html page; is AOC_SESSION_COOKIE valid?"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_input_cache() {
+    let dir = crate::test_dir("input-cache");
+    // A variable which is never set, so nothing is downloaded.
+    let cache = InputCache::new(&dir).with_cookie_var("AOC_TEST_NO_SUCH_COOKIE");
+    assert_eq!(cache.load(2021, 1).unwrap(), None);
+    assert!(cache.get(2021, 1).is_err());
+    cache.store(2021, 1, "199\n200\n").unwrap();
+    assert!(dir.join("2021/day01.txt").exists());
+    assert_eq!(cache.load(2021, 1).unwrap().as_deref(), Some("199\n200\n"));
+    assert_eq!(cache.get(2021, 1).unwrap(), "199\n200\n");
+    // Refreshing ignores the cached input, so tries to download.
+    assert!(cache.clone().with_refresh(true).get(2021, 1).is_err());
+
+    fs::write(cache.puzzle_path(2021, 1), "<article>Sonar</article>").unwrap();
+    assert_eq!(
+        cache.get_puzzle(2021, 1).unwrap(),
+        "<article>Sonar</article>"
+    );
+    cache.forget_puzzle(2021, 1).unwrap();
+    assert!(!cache.puzzle_path(2021, 1).exists());
+    cache.forget_puzzle(2021, 1).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let cache = InputCache::from_vars(Some("elsewhere".into()), Some("1".into()));
+    assert_eq!(cache.dir(), Path::new("elsewhere"));
+    assert!(cache.refresh());
+    let cache = InputCache::from_vars(None, Some("0".into()));
+    assert_eq!(cache.dir(), Path::new(DEFAULT_INPUT_DIR));
+    assert!(!cache.refresh());
+}
+
+#[test]
+fn test_validate_input() {
+    assert!(validate_input("<{([\n").is_ok());
+    assert!(validate_input(" \n").is_err());
+    assert!(validate_input("\n<!DOCTYPE html>\n<html>").is_err());
+    assert!(validate_input("<html><body>Log in</body></html>").is_err());
+}
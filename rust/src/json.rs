@@ -0,0 +1,222 @@
+use std::{iter::Peekable, str::Chars};
+
+use anyhow::{bail, ensure, Context, Result};
+
+/// How deeply arrays and objects may nest, so that hostile input cannot overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+/// A parsed JSON value. Objects keep their keys in order.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub(crate) fn parse(s: &str) -> Result<Self> {
+        let mut chars = s.chars().peekable();
+        let value = parse_value(&mut chars, 0)?;
+        skip_whitespace(&mut chars);
+        ensure!(chars.peek().is_none(), "unexpected data after JSON value");
+        Ok(value)
+    }
+    /// The value of `key`, if this is an object which has it.
+    pub(crate) fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Self::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    /// A non-negative integer, which may also be given as a string of digits.
+    pub(crate) fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Number(n) if *n >= 0.0 && *n < u64::MAX as f64 && n.fract() == 0.0 => {
+                Some(*n as u64)
+            }
+            Self::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+    pub(crate) fn as_object(&self) -> Option<&[(String, Json)]> {
+        match self {
+            Self::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+}
+
+fn expect(chars: &mut Peekable<Chars>, word: &str) -> Result<()> {
+    for c in word.chars() {
+        ensure!(chars.next() == Some(c), "expected '{}'", word);
+    }
+    Ok(())
+}
+
+fn parse_value(chars: &mut Peekable<Chars>, depth: usize) -> Result<Json> {
+    ensure!(
+        depth < MAX_DEPTH,
+        "JSON nested more than {} deep",
+        MAX_DEPTH
+    );
+    skip_whitespace(chars);
+    match chars.peek().copied().context("unexpected end of JSON")? {
+        'n' => expect(chars, "null").map(|_| Json::Null),
+        't' => expect(chars, "true").map(|_| Json::Bool(true)),
+        'f' => expect(chars, "false").map(|_| Json::Bool(false)),
+        '"' => parse_string(chars).map(Json::String),
+        '[' => {
+            chars.next();
+            let mut items = vec![];
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Ok(Json::Array(items));
+            }
+            loop {
+                items.push(parse_value(chars, depth + 1)?);
+                skip_whitespace(chars);
+                match chars.next() {
+                    Some(',') => continue,
+                    Some(']') => return Ok(Json::Array(items)),
+                    _ => bail!("expected ',' or ']' in array"),
+                }
+            }
+        }
+        '{' => {
+            chars.next();
+            let mut entries = vec![];
+            skip_whitespace(chars);
+            if chars.next_if_eq(&'}').is_some() {
+                return Ok(Json::Object(entries));
+            }
+            loop {
+                skip_whitespace(chars);
+                let key = parse_string(chars)?;
+                skip_whitespace(chars);
+                ensure!(
+                    chars.next() == Some(':'),
+                    "expected ':' after key {:?}",
+                    key
+                );
+                entries.push((key, parse_value(chars, depth + 1)?));
+                skip_whitespace(chars);
+                match chars.next() {
+                    Some(',') => continue,
+                    Some('}') => return Ok(Json::Object(entries)),
+                    _ => bail!("expected ',' or '}}' in object"),
+                }
+            }
+        }
+        c if c == '-' || c.is_ascii_digit() => {
+            let mut number = String::new();
+            while let Some(c) =
+                chars.next_if(|&c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+            {
+                number.push(c);
+            }
+            let n = number
+                .parse()
+                .with_context(|| format!("invalid number '{}'", number))?;
+            Ok(Json::Number(n))
+        }
+        c => bail!("unexpected '{}' in JSON", c),
+    }
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String> {
+    ensure!(chars.next() == Some('"'), "expected a string");
+    let mut s = String::new();
+    loop {
+        match chars.next().context("unterminated string")? {
+            '"' => return Ok(s),
+            '\\' => match chars.next().context("unterminated string")? {
+                'n' => s.push('\n'),
+                't' => s.push('\t'),
+                'r' => s.push('\r'),
+                'b' => s.push('\u{8}'),
+                'f' => s.push('\u{c}'),
+                c @ ('"' | '\\' | '/') => s.push(c),
+                'u' => {
+                    let mut code = parse_hex(chars)?;
+                    if (0xd800..0xdc00).contains(&code) {
+                        expect(chars, "\\u")?;
+                        let low = parse_hex(chars)?;
+                        ensure!(
+                            (0xdc00..0xe000).contains(&low),
+                            "unpaired surrogate '\\u{:04x}'",
+                            code
+                        );
+                        code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                    }
+                    let c = char::from_u32(code)
+                        .with_context(|| format!("unpaired surrogate '\\u{:04x}'", code))?;
+                    s.push(c);
+                }
+                c => bail!("invalid escape '\\{}'", c),
+            },
+            c => s.push(c),
+        }
+    }
+}
+
+/// Exactly four hex digits; `from_str_radix` alone would also take a sign.
+fn parse_hex(chars: &mut Peekable<Chars>) -> Result<u32> {
+    let hex: String = chars.by_ref().take(4).collect();
+    ensure!(
+        hex.len() == 4 && hex.bytes().all(|b| b.is_ascii_hexdigit()),
+        "invalid escape '\\u{}'",
+        hex
+    );
+    Ok(u32::from_str_radix(&hex, 16)?)
+}
+
+#[test]
+fn test_parse_json() {
+    let json =
+        Json::parse(r#" {"a": [1, -2.5e1, true, null], "b": "x\"\u00e9😀", "c": {}} "#).unwrap();
+    assert_eq!(
+        json.get("a"),
+        Some(&Json::Array(vec![
+            Json::Number(1.0),
+            Json::Number(-25.0),
+            Json::Bool(true),
+            Json::Null
+        ]))
+    );
+    assert_eq!(json.get("b").and_then(Json::as_str), Some("x\"é😀"));
+    assert_eq!(json.get("c").and_then(Json::as_object), Some(&[][..]));
+    assert_eq!(Json::String("17".into()).as_u64(), Some(17));
+    assert!(Json::parse("{\"a\": 1,}").is_err());
+    assert!(Json::parse("[1] 2").is_err());
+    assert!(Json::parse("\"abc").is_err());
+    assert!(Json::parse(r#""\q""#).is_err());
+    assert!(Json::parse(r#""\ud83d""#).is_err());
+    assert!(Json::parse(r#""\ud83d\u0041""#).is_err());
+    assert!(Json::parse(r#""\ude00""#).is_err());
+    assert!(Json::parse(r#""\u+041""#).is_err());
+    assert!(Json::parse(r#""\u004""#).is_err());
+    assert!(Json::parse(r#""\u00é""#).is_err());
+    assert_eq!(
+        Json::parse(r#""\u004A\u004a""#).unwrap(),
+        Json::String("JJ".into())
+    );
+    assert_eq!(
+        Json::parse(r#""\/\\\ud83d\ude00""#).unwrap(),
+        Json::String("/\\😀".into())
+    );
+    assert!(Json::parse(&"[".repeat(100_000)).is_err());
+    assert!(Json::parse(&format!("{}{}", "[".repeat(100), "]".repeat(100))).is_ok());
+}
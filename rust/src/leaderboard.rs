@@ -0,0 +1,290 @@
+use std::{collections::BTreeMap, fs, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+
+use crate::{input, json::Json};
+
+/// A private leaderboard, as exported by the site's JSON API.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub year: u16,
+    pub members: Vec<Member>,
+}
+
+/// One member of a [`Leaderboard`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users.
+    pub name: Option<String>,
+    /// The local score, as reported by the site.
+    pub local_score: u64,
+    pub stars: u64,
+    /// When each star was earned, by day, as a Unix timestamp in seconds.
+    pub star_times: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+/// The standings after a day: each member's index in [`Leaderboard::members`] and local
+/// score counting only the stars up to that day, best first.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Standings {
+    pub day: u8,
+    pub scores: Vec<(usize, u64)>,
+}
+
+/// How hard a day was for the leaderboard. Times are from the puzzle unlocking.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DayStats {
+    pub day: u8,
+    /// The number of members with each star.
+    pub stars: [usize; 2],
+    /// The median time to each star.
+    pub median_times: [Option<Duration>; 2],
+    /// The median time from part 1 to part 2, for members with both stars.
+    pub median_delay: Option<Duration>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self> {
+        let json = Json::parse(json).context("invalid JSON")?;
+        let members = json
+            .get("members")
+            .and_then(Json::as_object)
+            .context("missing or invalid 'members'")?
+            .iter()
+            .map(|(id, member)| {
+                Member::parse(member).with_context(|| format!("invalid member {}", id))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            owner_id: number(&json, "owner_id")?,
+            year: u16::try_from(number(&json, "event")?).context("invalid 'event'")?,
+            members,
+        })
+    }
+    /// Parse a saved leaderboard export.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&json).with_context(|| format!("failed to parse {}", path.display()))
+    }
+    /// When the given day's puzzle unlocked: midnight EST, as a Unix timestamp.
+    pub fn unlock_time(&self, day: u8) -> u64 {
+        let days = days_from_civil(self.year as i64, 12, day as i64);
+        (days * 86400 + 5 * 3600) as u64
+    }
+    /// The days on which any member earned a star, in order.
+    pub fn days(&self) -> Vec<u8> {
+        let mut days: Vec<u8> = self
+            .members
+            .iter()
+            .flat_map(|member| member.star_times.keys().copied())
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+        days
+    }
+    /// The time from the puzzle unlocking to the star for `level` (1 or 2).
+    pub fn solve_time(&self, member: &Member, day: u8, level: u8) -> Option<Duration> {
+        let time = member.star_time(day, level)?;
+        Some(Duration::from_secs(
+            time.saturating_sub(self.unlock_time(day)),
+        ))
+    }
+    /// The standings after each day, scored like the site: for every star, each member
+    /// gets one point per member, minus one per member who earned it before them.
+    pub fn standings(&self) -> Vec<Standings> {
+        let mut scores = vec![0; self.members.len()];
+        self.days()
+            .into_iter()
+            .map(|day| {
+                for level in 1..=2 {
+                    let mut solvers: Vec<(u64, usize)> = self
+                        .members
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(i, m)| Some((m.star_time(day, level)?, i)))
+                        .collect();
+                    solvers.sort_unstable();
+                    for (rank, &(_, i)) in solvers.iter().enumerate() {
+                        scores[i] += (self.members.len() - rank) as u64;
+                    }
+                }
+                let mut ranked: Vec<(usize, u64)> = scores.iter().copied().enumerate().collect();
+                ranked.sort_by_key(|&(i, score)| (std::cmp::Reverse(score), i));
+                Standings {
+                    day,
+                    scores: ranked,
+                }
+            })
+            .collect()
+    }
+    /// Statistics for each day on which any member earned a star.
+    pub fn difficulty(&self) -> Vec<DayStats> {
+        self.days()
+            .into_iter()
+            .map(|day| {
+                let times = |level| -> Vec<Duration> {
+                    self.members
+                        .iter()
+                        .filter_map(|m| self.solve_time(m, day, level))
+                        .collect()
+                };
+                let (part1, part2) = (times(1), times(2));
+                let delays = self
+                    .members
+                    .iter()
+                    .filter_map(|m| m.part2_delay(day))
+                    .collect();
+                DayStats {
+                    day,
+                    stars: [part1.len(), part2.len()],
+                    median_times: [median(part1), median(part2)],
+                    median_delay: median(delays),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Member {
+    fn parse(member: &Json) -> Result<Self> {
+        let mut star_times = BTreeMap::new();
+        let days = member
+            .get("completion_day_level")
+            .and_then(Json::as_object)
+            .unwrap_or_default();
+        for (day, levels) in days {
+            let day: u8 = day
+                .parse()
+                .with_context(|| format!("invalid day '{}'", day))?;
+            let time = |level| {
+                levels
+                    .get(level)
+                    .map(|star| number(star, "get_star_ts"))
+                    .transpose()
+            };
+            star_times.insert(day, [time("1")?, time("2")?]);
+        }
+        Ok(Self {
+            id: number(member, "id")?,
+            name: member.get("name").and_then(Json::as_str).map(Into::into),
+            local_score: number(member, "local_score")?,
+            stars: number(member, "stars")?,
+            star_times,
+        })
+    }
+    /// The name shown by the site, which numbers anonymous users.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+    /// When the star for `level` (1 or 2) of the given day was earned.
+    pub fn star_time(&self, day: u8, level: u8) -> Option<u64> {
+        let times = self.star_times.get(&day)?;
+        *times.get((level as usize).checked_sub(1)?)?
+    }
+    /// The time between earning the two stars of the given day.
+    pub fn part2_delay(&self, day: u8) -> Option<Duration> {
+        let (part1, part2) = (self.star_time(day, 1)?, self.star_time(day, 2)?);
+        Some(Duration::from_secs(part2.saturating_sub(part1)))
+    }
+}
+
+/// Download a private leaderboard's JSON export with the session cookie.
+///
+/// The site asks that this is done at most once every 15 minutes, so save the result.
+pub fn download_leaderboard(year: u16, id: u64) -> Result<String> {
+    input::load_dotenv();
+    let cookie = input::session_cookie()?;
+    download_leaderboard_from(&input::base_url(), &cookie, year, id)
+}
+
+/// Download a private leaderboard's JSON export from the site at `base_url`, with `cookie`
+/// as the `Cookie` header.
+pub fn download_leaderboard_from(
+    base_url: &str,
+    cookie: &str,
+    year: u16,
+    id: u64,
+) -> Result<String> {
+    let url = format!("{}/{}/leaderboard/private/view/{}.json", base_url, year, id);
+    ureq::get(&url)
+        .set("Cookie", cookie)
+        .call()
+        .context("http request error")?
+        .into_string()
+        .context("http response error")
+}
+
+fn number(json: &Json, key: &str) -> Result<u64> {
+    json.get(key)
+        .and_then(Json::as_u64)
+        .with_context(|| format!("missing or invalid '{}'", key))
+}
+
+fn median(mut times: Vec<Duration>) -> Option<Duration> {
+    times.sort_unstable();
+    let mid = times.len() / 2;
+    match times.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((times[mid - 1] + times[mid]) / 2),
+        _ => Some(times[mid]),
+    }
+}
+
+/// The number of days from 1970-01-01 to the given date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[test]
+fn test_leaderboard() {
+    // 2021-12-01 05:00 UTC
+    let unlock = 1638334800;
+    let json = format!(
+        r#"{{"owner_id":1,"event":"2021","members":{{
+        "1":{{"id":1,"name":"Ann","stars":3,"local_score":8,"global_score":0,"last_star_ts":0,
+            "completion_day_level":{{
+                "1":{{"1":{{"get_star_ts":{a1},"star_index":1}},"2":{{"get_star_ts":{a2},"star_index":3}}}},
+                "2":{{"1":{{"get_star_ts":{b1},"star_index":5}}}}}}}},
+        "2":{{"id":2,"name":null,"stars":2,"local_score":5,"global_score":0,"last_star_ts":0,
+            "completion_day_level":{{
+                "1":{{"1":{{"get_star_ts":{c1},"star_index":2}},"2":{{"get_star_ts":{c2},"star_index":4}}}}}}}},
+        "3":{{"id":3,"name":"Cy","stars":0,"local_score":0,"global_score":0,"last_star_ts":0,
+            "completion_day_level":{{}}}}}}}}"#,
+        a1 = unlock + 600,
+        a2 = unlock + 900,
+        b1 = unlock + 86400 + 60,
+        c1 = unlock + 300,
+        c2 = unlock + 1500,
+    );
+    let leaderboard = Leaderboard::parse(&json).unwrap();
+    assert_eq!(leaderboard.unlock_time(1), unlock);
+    assert_eq!(leaderboard.days(), [1, 2]);
+    let ann = &leaderboard.members[0];
+    assert_eq!(ann.part2_delay(1), Some(Duration::from_secs(300)));
+    assert_eq!(ann.part2_delay(2), None);
+    assert_eq!(leaderboard.members[1].display_name(), "(anonymous user #2)");
+
+    let standings = leaderboard.standings();
+    assert_eq!(standings[0].scores, [(0, 5), (1, 5), (2, 0)]);
+    assert_eq!(standings[1].scores, [(0, 8), (1, 5), (2, 0)]);
+    assert_eq!(standings[1].scores[0].1, leaderboard.members[0].local_score);
+
+    let day1 = &leaderboard.difficulty()[0];
+    assert_eq!(day1.stars, [2, 2]);
+    assert_eq!(day1.median_times[0], Some(Duration::from_secs(450)));
+    assert_eq!(day1.median_delay, Some(Duration::from_secs(750)));
+    assert!(Leaderboard::parse("{}").is_err());
+    assert!(Leaderboard::parse(&json.replace("\"2021\"", "\"67557\"")).is_err());
+}
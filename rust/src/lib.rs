@@ -1,80 +1,516 @@
+#![feature(array_windows, available_parallelism, drain_filter, int_abs_diff)]
+
 use std::{
+    any::Any,
     cmp,
-    fmt::{Debug, Display},
+    fmt::{self, Debug, Display},
+    fs::File,
+    io::{BufRead, BufReader},
     iter::{self, FusedIterator},
     ops::{Index, IndexMut},
+    panic,
+    path::Path,
     slice,
-    time::Instant,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, ensure, Context, Result};
+
+mod allocations;
+mod answers;
+mod bench;
+pub mod cli;
+pub mod days;
+mod fuzz;
+mod input;
+mod json;
+mod leaderboard;
+mod normalize;
+mod options;
+mod parse;
+mod profile;
+mod puzzle;
+mod report;
+mod scaffold;
+mod submit;
+pub mod trace;
+mod watch;
+
+pub use allocations::{count_allocations, AllocStats, CountingAllocator};
+pub use answers::{AnswerRegistry, KnownAnswers, Verdict, DEFAULT_ANSWERS_DIR};
+// For the macros, so that crates which use them need not depend on `anyhow`.
+#[doc(hidden)]
+pub use anyhow;
+pub use bench::{bench, BenchConfig, BenchStats};
+pub use fuzz::{fuzz, mutate, FuzzFailure, Rng};
+pub use input::{
+    download_input, download_input_with, InputCache, DEFAULT_BASE_URL, DEFAULT_INPUT_DIR,
 };
+pub use leaderboard::{
+    download_leaderboard, download_leaderboard_from, DayStats, Leaderboard, Member, Standings,
+};
+pub use normalize::{InputChanges, Normalize};
+pub use options::{InputSource, Options};
+pub use parse::{numbered_lines, Line, LineReader, ParseError};
+pub use profile::Profile;
+pub use puzzle::{download_puzzle, download_puzzle_with, render_puzzle, PuzzlePage, PuzzlePart};
+pub use report::{profile, Format, Record};
+pub use scaffold::Scaffold;
+pub use submit::{submit_answer, submit_answer_to, Outcome};
+pub use watch::{watch, DEFAULT_WATCH_INTERVAL};
+
+/// The year of a [`Solution`] which does not set [`Solution::YEAR`].
+pub const DEFAULT_YEAR: u16 = 2021;
 
-use anyhow::{ensure, Context, Result};
+/// A new, empty directory for a test's files.
+#[cfg(test)]
+pub(crate) fn test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc2021-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
 
 /// Common functionality for a day's solution.
 pub trait Solution {
+    /// The event year
+    const YEAR: u16 = DEFAULT_YEAR;
     /// The day number
     const DAY: u8;
     /// The concrete type returned for part 1
     type Out1: Display + Debug;
     /// The concrete type returned for part 2
     type Out2: Display + Debug;
+    /// The most a release build may take to solve the real input, if the solution has a
+    /// budget. The runner and tests fail when it is exceeded (and warn in debug builds).
+    const BUDGET: Option<Duration> = None;
 
     /// A function which should solve both parts and return the correct values
     fn solve(input: String) -> Result<(Self::Out1, Self::Out2)>;
+
+    /// Solve the selected parts (both when `only` is `None`), timing each phase.
+    ///
+    /// By default both parts are solved together by [`solve`](Self::solve), and timed as one
+    /// phase. Days implementing [`PhasedSolution`] time parsing and each part separately.
+    fn solve_timed(input: String, only: Option<u8>) -> Result<Solved<Self::Out1, Self::Out2>> {
+        let time = Instant::now();
+        let (part1, part2) = Self::solve(input)?;
+        let time = time.elapsed();
+        Ok(Solved {
+            part1: (only != Some(2)).then(|| part1),
+            part2: (only != Some(1)).then(|| part2),
+            timings: PhaseTimings {
+                total: time,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// The examples declared in [`day!`], with each answer as it would be printed, for
+    /// [`watch`](watch()) to check.
+    fn examples() -> Vec<Example<String, String>> {
+        Vec::new()
+    }
 }
 
-/// Retrieve the real input for the given day.
+/// A solution split into a parsing phase and a function for each part.
 ///
-/// Requires a run-time environment variable named `AOC_SESSION_COOKIE`.
-/// If this variable is not set, the function will fail.
-pub fn get_input(day: u8) -> Result<String> {
-    if dotenv::dotenv().is_err() {
-        eprintln!("WARN: failed to load .env file")
+/// Every `PhasedSolution` is also a [`Solution`], so parsing and each part can be timed
+/// separately, and either part can be run on its own.
+pub trait PhasedSolution {
+    /// The event year
+    const YEAR: u16 = DEFAULT_YEAR;
+    /// The day number
+    const DAY: u8;
+    /// The parsed input, shared by both parts
+    type Parsed;
+    /// The concrete type returned for part 1
+    type Out1: Display + Debug;
+    /// The concrete type returned for part 2
+    type Out2: Display + Debug;
+    /// See [`Solution::BUDGET`].
+    const BUDGET: Option<Duration> = None;
+
+    fn parse(input: String) -> Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Out1>;
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Out2>;
+
+    /// See [`Solution::examples`].
+    fn examples() -> Vec<Example<String, String>> {
+        Vec::new()
+    }
+}
+
+impl<T: PhasedSolution> Solution for T {
+    const YEAR: u16 = <T as PhasedSolution>::YEAR;
+    const DAY: u8 = <T as PhasedSolution>::DAY;
+    type Out1 = <T as PhasedSolution>::Out1;
+    type Out2 = <T as PhasedSolution>::Out2;
+    const BUDGET: Option<Duration> = <T as PhasedSolution>::BUDGET;
+
+    fn solve(input: String) -> Result<(Self::Out1, Self::Out2)> {
+        let parsed = T::parse(input)?;
+        Ok((T::part1(&parsed)?, T::part2(&parsed)?))
+    }
+
+    fn solve_timed(input: String, only: Option<u8>) -> Result<Solved<Self::Out1, Self::Out2>> {
+        let start = Instant::now();
+        let parsed = T::parse(input)?;
+        let parse = start.elapsed();
+        let timed = |part: u8| only.map_or(true, |only| only == part);
+        let (part1, part1_time) = match timed(1) {
+            true => {
+                let time = Instant::now();
+                (Some(T::part1(&parsed)?), Some(time.elapsed()))
+            }
+            false => (None, None),
+        };
+        let (part2, part2_time) = match timed(2) {
+            true => {
+                let time = Instant::now();
+                (Some(T::part2(&parsed)?), Some(time.elapsed()))
+            }
+            false => (None, None),
+        };
+        Ok(Solved {
+            part1,
+            part2,
+            timings: PhaseTimings {
+                parse: Some(parse),
+                part1: part1_time,
+                part2: part2_time,
+                total: start.elapsed(),
+            },
+        })
+    }
+
+    fn examples() -> Vec<Example<String, String>> {
+        <T as PhasedSolution>::examples()
+    }
+}
+
+/// A solution which can read its input in a single pass, without holding all of it in
+/// memory, for inputs too large to read into a `String`.
+pub trait StreamingSolution: Solution {
+    fn solve_reader(reader: impl BufRead) -> Result<(Self::Out1, Self::Out2)>;
+
+    /// Solve the input in the file at `path`, streaming it from disk.
+    fn solve_file(path: impl AsRef<Path>) -> Result<(Self::Out1, Self::Out2)> {
+        let path = path.as_ref();
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        Self::solve_reader(BufReader::new(file))
     }
+}
+
+/// The answers from [`Solution::solve_timed`]; a part is `None` when it was not selected.
+#[derive(Clone, Debug)]
+pub struct Solved<A, B> {
+    pub part1: Option<A>,
+    pub part2: Option<B>,
+    pub timings: PhaseTimings,
+}
+
+/// How long each phase of a solve took. Phases which were not timed separately are `None`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PhaseTimings {
+    pub parse: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+    pub total: Duration,
+}
 
-    let session_cookie_value = std::env::var("AOC_SESSION_COOKIE")
-        .context("failed to read AOC_SESSION_COOKIE from environment")?;
+impl Display for PhaseTimings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let phases = [
+            ("Parse", self.parse),
+            ("Part 1", self.part1),
+            ("Part 2", self.part2),
+        ];
+        let phases: Vec<String> = phases
+            .iter()
+            .filter_map(|(name, time)| time.map(|time| format!("{} {:.2?}", name, time)))
+            .collect();
+        if phases.is_empty() {
+            write!(f, "Total {:.2?}", self.total)
+        } else {
+            write!(f, "{} | Total {:.2?}", phases.join(" | "), self.total)
+        }
+    }
+}
 
-    let url = format!("https://adventofcode.com/2021/day/{}/input", day);
-    let data = ureq::get(&url)
-        .set("Cookie", &format!("session={}", session_cookie_value))
-        .call()
-        .context("http request error")?
-        .into_string()
-        .context("http response error")?;
-    Ok(data)
+/// Retrieve the real input for the given year and day.
+///
+/// The input is read from the on-disk cache when available (see [`InputCache::from_env`]),
+/// otherwise it is downloaded and written to the cache.
+///
+/// Downloading requires a run-time environment variable named `AOC_SESSION_COOKIE`.
+/// If this variable is not set, the function will fail.
+pub fn get_input(year: u16, day: u8) -> Result<String> {
+    InputCache::from_env().get(year, day)
 }
 
 /// Run the solution and print the results.
+///
+/// The input source is chosen by the command-line arguments, see [`Options`].
 pub fn run_solution<S: Solution>() -> Result<()> {
-    let input = get_input(S::DAY)
-        .with_context(|| format!("failed to retrieve input for day {:02}", S::DAY))?;
+    run::<S>(None)
+}
+
+/// Like [`run_solution`], but `--stream` solves the input file or stdin as it is read,
+/// with [`StreamingSolution::solve_reader`].
+pub fn run_streaming_solution<S: StreamingSolution>() -> Result<()> {
+    run::<S>(Some(stream_input::<S>))
+}
+
+/// Solves the input of `S` as it is read from where the options say.
+type Stream<S> = fn(&Options) -> Result<(<S as Solution>::Out1, <S as Solution>::Out2)>;
 
-    let time = Instant::now();
+fn stream_input<S: StreamingSolution>(options: &Options) -> Result<(S::Out1, S::Out2)> {
+    match &options.input {
+        InputSource::File(path) => S::solve_file(path),
+        InputSource::Stdin => S::solve_reader(std::io::stdin().lock()),
+        _ => bail!("--stream needs --input"),
+    }
+}
+
+fn run<S: Solution>(stream: Option<Stream<S>>) -> Result<()> {
+    let options = Options::from_args()?;
+    if options.trace.is_some() {
+        trace::set_level(options.trace);
+    }
+    if options.format != Format::Text {
+        let mut records = [record::<S>(&options, stream)];
+        if !cfg!(debug_assertions) && !options.stream {
+            let runs = [(days::Day::of::<S>(), options.clone())];
+            days::retime_over_budget(&runs, &mut records);
+        }
+        let [record] = records;
+        print_records(options.format, &[record.clone()])?;
+        ensure!(!record.failed(), "{} day {:02} failed", S::YEAR, S::DAY);
+        return Ok(());
+    }
 
-    let (part1, part2) = S::solve(input)?;
+    let name = format!("{} Day {:02}", S::YEAR, S::DAY);
+    // A streamed input is never held in memory, so it cannot be benchmarked either.
+    let input = match options.stream {
+        true => None,
+        false => Some(
+            options
+                .read_input(S::YEAR, S::DAY)
+                .with_context(|| format!("failed to retrieve input for {}", name))?,
+        ),
+    };
 
-    let time = time.elapsed();
+    let mut record = match &input {
+        Some(input) => solve_record::<S>(input.clone(), &options)?,
+        None => stream_record::<S>(stream, &options)?,
+    };
+    if let Some(input) = &input {
+        retime::<S>(&mut record, input, &options)?;
+    }
+    // Only the solve itself, not checking answers or counting allocations.
+    let time = record.timings.map_or(Duration::ZERO, |t| t.total);
 
-    println!("Day {:02} - Part 1:\n{}", S::DAY, part1);
-    println!("Day {:02} - Part 2:\n{}", S::DAY, part2);
+    if let Some(part1) = &record.part1 {
+        println!("{} - Part 1:\n{}", name, part1);
+    }
+    if let Some(part2) = &record.part2 {
+        println!("{} - Part 2:\n{}", name, part2);
+    }
     println!(
         "Run took {:.5}s | {}ms | {}μs ({})",
         time.as_secs_f32(),
         time.as_millis(),
         time.as_micros(),
-        if cfg!(debug_assertions) {
-            "DEBUG"
-        } else {
-            "RELEASE"
+        report::profile()
+    );
+    if let Some(timings) = record.timings.filter(|t| t.parse.is_some()) {
+        println!("Phases: {}", timings);
+    }
+    if let Some(allocations) = &record.allocations {
+        println!("Allocations: {}", allocations);
+    }
+
+    if options.lock {
+        println!(
+            "{} - Answers recorded in {}",
+            name,
+            options.answers().path(S::YEAR, S::DAY).display()
+        );
+    }
+    for (level, verdict) in (1..=2).zip(&record.verdicts) {
+        if let Some(verdict) = verdict {
+            println!("{} - Part {}: {}", name, level, verdict);
         }
+    }
+    ensure!(
+        !record.verdicts.iter().flatten().any(Verdict::is_fail),
+        "{} does not match the known answers",
+        name
     );
+    // Missing the budget is only a warning here, so that a slow answer can still be submitted.
+    if let Some(budget) = record.budget.filter(|_| record.over_budget()) {
+        eprintln!(
+            "WARN: {} took longer than its release budget of {:.2?}",
+            name, budget
+        );
+    }
+
+    if let Some(level) = options.submit {
+        let answer = [&record.part1, &record.part2][level as usize - 1]
+            .as_ref()
+            .with_context(|| format!("part {} was not run", level))?;
+        let outcome = match &options.profile {
+            Some(profile) => profile.cookie().and_then(|cookie| {
+                submit_answer_to(&input::base_url(), &cookie, S::YEAR, S::DAY, level, answer)
+            }),
+            None => submit_answer(S::YEAR, S::DAY, level, answer),
+        }
+        .with_context(|| format!("failed to submit part {} for {}", level, name))?;
+        println!("{} - Part {} submitted: {}", name, level, outcome);
+        if outcome == Outcome::Correct {
+            options.answers().record(S::YEAR, S::DAY, level, answer)?;
+            options.input_cache().forget_puzzle(S::YEAR, S::DAY)?;
+        }
+    }
+
+    if let (Some(config), Some(input)) = (&options.bench, &input) {
+        let stats = bench(config, input, |input| S::solve_timed(input, options.part))?;
+        println!("{} - Benchmark:\n{}", name, stats);
+    }
 
     Ok(())
 }
 
+/// When a release build is over budget, solve [`days::BUDGET_RUNS`] more times, one after
+/// another, and keep the fastest time, as the runner does.
+fn retime<S: Solution>(record: &mut Record, input: &str, options: &Options) -> Result<()> {
+    if !record.over_budget() || cfg!(debug_assertions) {
+        return Ok(());
+    }
+    for _ in 0..days::BUDGET_RUNS {
+        let timings = S::solve_timed(input.to_owned(), options.part)?.timings;
+        if record.timings.map_or(true, |t| timings.total < t.total) {
+            record.timings = Some(timings);
+        }
+    }
+    Ok(())
+}
+
+/// Run the solution as configured by `options`, capturing the answers, timings, verdicts
+/// and any error in a [`Record`] instead of printing them.
+pub fn record_solution<S: Solution>(options: &Options) -> Record {
+    record::<S>(options, None)
+}
+
+/// Like [`record_solution`], but streams the input when `options.stream` is set, see
+/// [`run_streaming_solution`].
+pub fn record_streaming_solution<S: StreamingSolution>(options: &Options) -> Record {
+    record::<S>(options, Some(stream_input::<S>))
+}
+
+fn record<S: Solution>(options: &Options, stream: Option<Stream<S>>) -> Record {
+    let record = match options.stream {
+        true => stream_record::<S>(stream, options),
+        false => options
+            .read_input(S::YEAR, S::DAY)
+            .context("failed to retrieve input")
+            .and_then(|input| solve_record::<S>(input, options)),
+    };
+    record.unwrap_or_else(|e| Record {
+        error: Some(format!("{:#}", e)),
+        ..Record::new(S::YEAR, S::DAY)
+    })
+}
+
+/// Solve, then lock and/or verify the answers as configured.
+fn solve_record<S: Solution>(input: String, options: &Options) -> Result<Record> {
+    finish_record::<S, _>(|| S::solve_timed(input, options.part), options)
+}
+
+/// Like [`solve_record`], but streaming the input; both parts are timed as one phase.
+fn stream_record<S: Solution>(stream: Option<Stream<S>>, options: &Options) -> Result<Record> {
+    let stream =
+        stream.with_context(|| format!("{} day {:02} cannot stream its input", S::YEAR, S::DAY))?;
+    let solve = || {
+        let time = Instant::now();
+        let (part1, part2) = stream(options)?;
+        Ok(Solved {
+            part1: (options.part != Some(2)).then(|| part1),
+            part2: (options.part != Some(1)).then(|| part2),
+            timings: PhaseTimings {
+                total: time.elapsed(),
+                ..Default::default()
+            },
+        })
+    };
+    finish_record::<S, _>(solve, options)
+}
+
+fn finish_record<S, F>(solve: F, options: &Options) -> Result<Record>
+where
+    S: Solution,
+    F: FnOnce() -> Result<Solved<S::Out1, S::Out2>>,
+{
+    let (solved, allocations) = count_allocations(solve);
+    let solved = solved?;
+    let answers = [
+        solved.part1.as_ref().map(ToString::to_string),
+        solved.part2.as_ref().map(ToString::to_string),
+    ];
+    let mut verdicts = [None, None];
+    if options.lock || options.verify {
+        let registry = options.answers();
+        if options.lock {
+            registry.lock(S::YEAR, S::DAY, &answers)?;
+        }
+        if options.verify {
+            verdicts = registry.verify(S::YEAR, S::DAY, &answers)?;
+        }
+    }
+    let [part1, part2] = answers;
+    Ok(Record {
+        year: S::YEAR,
+        day: S::DAY,
+        part1,
+        part2,
+        timings: Some(solved.timings),
+        budget: S::BUDGET.filter(|_| options.input == InputSource::Real),
+        allocations,
+        verdicts,
+        error: None,
+    })
+}
+
+/// Print records in a machine-readable format; CSV gets a header line first.
+///
+/// Fails for [`Format::Text`], which has no fixed layout for records.
+pub fn print_records(format: Format, records: &[Record]) -> Result<()> {
+    match format {
+        Format::Json => records.iter().for_each(|r| println!("{}", r.to_json())),
+        Format::Csv => {
+            println!("{}", Record::CSV_HEADER);
+            records.iter().for_each(|r| println!("{}", r.to_csv()));
+        }
+        Format::Text => bail!("records have no text format"),
+    }
+    Ok(())
+}
+
+/// The message of a panic, from the payload caught by `catch_unwind`.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
 /// Test the solution, given specific input and the expected answers.
 ///
-/// Will panic on error or incorrect output.
+/// Will panic on error or incorrect output, after printing everything the solution
+/// traced (see [`trace`]).
 pub fn test_solution<S, I, A1, A2>(input: I, (answer1, answer2): (A1, A2))
 where
     S: Solution,
@@ -84,20 +520,311 @@ where
     S::Out1: PartialEq<A1> + Debug,
     S::Out2: PartialEq<A2> + Debug,
 {
-    println!("Testing day {:02}", S::DAY);
-    let input = input.into();
+    println!("Testing {} day {:02}", S::YEAR, S::DAY);
+    // Examples pasted into tests rarely end with a newline, so fix them silently.
+    let (input, _) = Normalize::default().apply(&input.into());
     println!(
         "Input (starts on next line):\n{}\n(input ends on line above)",
         input
     );
 
-    let result = S::solve(input);
+    let (result, trace) = trace::capture(trace::Level::Trace, || {
+        panic::catch_unwind(panic::AssertUnwindSafe(|| S::solve(input)))
+    });
+    let print_trace = || {
+        if !trace.is_empty() {
+            println!(
+                "Trace (starts on next line):\n{}(trace ends on line above)",
+                trace
+            );
+        }
+    };
+    let result = match result {
+        Ok(result) => result,
+        Err(payload) => {
+            print_trace();
+            panic::resume_unwind(payload);
+        }
+    };
     println!("Got Result: {:?}", result);
+    let passed = matches!(&result, Ok((part1, part2)) if *part1 == answer1 && *part2 == answer2);
+    if !passed {
+        print_trace();
+    }
     let (part1, part2) = result.unwrap();
     assert_eq!(part1, answer1, "Part 1 failure");
     assert_eq!(part2, answer2, "Part 2 failure");
 }
 
+/// A named example for [`test_examples`]. A part which is `None` is not checked.
+#[derive(Clone, Debug)]
+pub struct Example<A1, A2> {
+    pub name: &'static str,
+    pub input: &'static str,
+    pub part1: Option<A1>,
+    pub part2: Option<A2>,
+}
+
+/// Test the solution against every example, and report every failure at the end.
+///
+/// Will panic if any example errors, panics or gives an incorrect answer.
+/// See [`test_examples!`] for declaring the examples as a table.
+pub fn test_examples<S, A1, A2>(examples: &[Example<A1, A2>])
+where
+    S: Solution,
+    A1: Debug,
+    A2: Debug,
+    S::Out1: PartialEq<A1> + Debug,
+    S::Out2: PartialEq<A2> + Debug,
+{
+    let mut failures = vec![];
+    for example in examples {
+        println!(
+            "Testing {} day {:02}, example {}",
+            S::YEAR,
+            S::DAY,
+            example.name
+        );
+        let (input, _) = Normalize::default().apply(example.input);
+        let result = panic::catch_unwind(|| S::solve(input));
+        println!("Got Result: {:?}", result);
+        let (part1, part2) = match result {
+            Ok(Ok(answers)) => answers,
+            Ok(Err(e)) => {
+                failures.push(format!("{}: error: {:#}", example.name, e));
+                continue;
+            }
+            Err(payload) => {
+                failures.push(format!(
+                    "{}: panicked: {}",
+                    example.name,
+                    panic_message(&*payload)
+                ));
+                continue;
+            }
+        };
+        if let Some(expected) = example.part1.as_ref().filter(|&a| part1 != *a) {
+            failures.push(format!(
+                "{}: part 1: expected {:?}, got {:?}",
+                example.name, expected, part1
+            ));
+        }
+        if let Some(expected) = example.part2.as_ref().filter(|&a| part2 != *a) {
+            failures.push(format!(
+                "{}: part 2: expected {:?}, got {:?}",
+                example.name, expected, part2
+            ));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {} examples failed:\n{}",
+        failures.len(),
+        examples.len(),
+        failures.join("\n")
+    );
+}
+
+/// Declare a `#[test] fn test_examples` checking a solution against a table of examples.
+///
+/// Each example is `name: input => (part1, part2)`, where an answer of `_` is not checked.
+/// Answers which are not a single token, such as `-5`, must be wrapped in parentheses.
+/// A table given to [`day!`] is also returned by [`Solution::examples`], for
+/// [`watch`](watch()).
+///
+/// ```ignore
+/// crate::test_examples!(Day12 {
+///     small: SMALL => (10, 36),
+///     part1_only: "start-end" => (1, _),
+/// });
+/// ```
+#[macro_export]
+macro_rules! test_examples {
+    // An unchecked part has the part's type, which any answer type can be compared with.
+    (@part $out:ty, _) => {
+        None::<$out>
+    };
+    (@part $out:ty, $answer:expr) => {
+        Some($answer)
+    };
+    ($solution:ty { $($name:ident: $input:expr => ($part1:tt, $part2:tt)),+ $(,)? }) => {
+        #[test]
+        fn test_examples() {
+            $crate::test_examples::<$solution, _, _>(&[$($crate::Example {
+                name: stringify!($name),
+                input: $input,
+                part1: $crate::test_examples!(@part <$solution as $crate::Solution>::Out1, $part1),
+                part2: $crate::test_examples!(@part <$solution as $crate::Solution>::Out2, $part2),
+            }),+]);
+        }
+    };
+}
+
+/// Define a day: its unit struct, its [`Solution`] (or [`PhasedSolution`]) impl, and
+/// optionally a [`test_examples!`] table (also returned by [`Solution::examples`]), leaving
+/// only the solving logic to write.
+///
+/// The year is [`DEFAULT_YEAR`] unless it is given before `day`, and a
+/// [`BUDGET`](Solution::BUDGET) may follow the parts' types. The function bodies
+/// return [`anyhow::Result`]s, and the parts' types are given after `->`.
+///
+/// A day which reads its input line by line defines `fn solve_reader(reader)` instead of
+/// `fn solve`, and is also a [`StreamingSolution`]; a phased day may add one after
+/// `part2` to stream its input separately.
+///
+/// ```ignore
+/// crate::day! {
+///     Day6 = day 6 -> (u64, u64);
+///     examples {
+///         example: "3,4,3,1,2" => (5934, 26984457539),
+///     }
+///     fn solve(input) {
+///         ...
+///     }
+/// }
+///
+/// crate::day! {
+///     Day7 = 2021 day 7 -> (u64, u64), budget Duration::from_millis(5);
+///     type Parsed = Vec<u32>;
+///     fn parse(input) { ... }
+///     fn part1(crabs) { ... }
+///     fn part2(crabs) { ... }
+/// }
+/// ```
+///
+/// A day's binary is then just `aoc2021::main!(aoc2021::days::Day6);`.
+#[macro_export]
+macro_rules! day {
+    (@examples) => {};
+    (@examples $($name:ident: $input:expr => ($part1:tt, $part2:tt)),+ $(,)?) => {
+        fn examples() -> Vec<$crate::Example<String, String>> {
+            vec![$($crate::Example {
+                name: stringify!($name),
+                input: $input,
+                part1: $crate::day!(@answer Self::Out1, $part1),
+                part2: $crate::day!(@answer Self::Out2, $part2),
+            }),+]
+        }
+    };
+    (@answer $out:ty, _) => {
+        None
+    };
+    (@answer $out:ty, $answer:expr) => {{
+        let answer = $answer;
+        // Give a literal the part's type, as comparing it with an answer does in tests.
+        let _ = |out: &$out| *out == answer;
+        Some(ToString::to_string(&answer))
+    }};
+    (
+        $(#[$meta:meta])*
+        $name:ident = $($year:literal)? day $day:literal -> ($out1:ty, $out2:ty)
+            $(, budget $budget:expr)?;
+        $(examples { $($examples:tt)* })?
+        $(#[$solve_meta:meta])*
+        fn solve($input:ident) $solve:block
+    ) => {
+        $(#[$meta])*
+        pub struct $name;
+        impl $crate::Solution for $name {
+            $(const YEAR: u16 = $year;)?
+            const DAY: u8 = $day;
+            type Out1 = $out1;
+            type Out2 = $out2;
+            $(const BUDGET: Option<::std::time::Duration> = Some($budget);)?
+
+            $(#[$solve_meta])*
+            fn solve($input: String) -> $crate::anyhow::Result<(Self::Out1, Self::Out2)> $solve
+            $crate::day!(@examples $($($examples)*)?);
+        }
+        $($crate::test_examples!($name { $($examples)* });)?
+    };
+    (
+        $(#[$meta:meta])*
+        $name:ident = $($year:literal)? day $day:literal -> ($out1:ty, $out2:ty)
+            $(, budget $budget:expr)?;
+        $(examples { $($examples:tt)* })?
+        $(#[$solve_meta:meta])*
+        fn solve_reader($reader:ident) $solve:block
+    ) => {
+        $(#[$meta])*
+        pub struct $name;
+        impl $crate::Solution for $name {
+            $(const YEAR: u16 = $year;)?
+            const DAY: u8 = $day;
+            type Out1 = $out1;
+            type Out2 = $out2;
+            $(const BUDGET: Option<::std::time::Duration> = Some($budget);)?
+
+            fn solve(input: String) -> $crate::anyhow::Result<(Self::Out1, Self::Out2)> {
+                <Self as $crate::StreamingSolution>::solve_reader(input.as_bytes())
+            }
+            $crate::day!(@examples $($($examples)*)?);
+        }
+        impl $crate::StreamingSolution for $name {
+            $(#[$solve_meta])*
+            fn solve_reader(
+                $reader: impl ::std::io::BufRead,
+            ) -> $crate::anyhow::Result<(Self::Out1, Self::Out2)> $solve
+        }
+        $($crate::test_examples!($name { $($examples)* });)?
+    };
+    (
+        $(#[$meta:meta])*
+        $name:ident = $($year:literal)? day $day:literal -> ($out1:ty, $out2:ty)
+            $(, budget $budget:expr)?;
+        $(examples { $($examples:tt)* })?
+        $(#[$parsed_meta:meta])*
+        type Parsed = $parsed:ty;
+        fn parse($input:ident) $parse:block
+        fn part1($parsed1:ident) $part1:block
+        fn part2($parsed2:ident) $part2:block
+        $(
+            $(#[$stream_meta:meta])*
+            fn solve_reader($reader:ident) $stream:block
+        )?
+    ) => {
+        $(#[$meta])*
+        pub struct $name;
+        impl $crate::PhasedSolution for $name {
+            $(const YEAR: u16 = $year;)?
+            const DAY: u8 = $day;
+            $(#[$parsed_meta])*
+            type Parsed = $parsed;
+            type Out1 = $out1;
+            type Out2 = $out2;
+            $(const BUDGET: Option<::std::time::Duration> = Some($budget);)?
+
+            fn parse($input: String) -> $crate::anyhow::Result<Self::Parsed> $parse
+            fn part1($parsed1: &Self::Parsed) -> $crate::anyhow::Result<Self::Out1> $part1
+            fn part2($parsed2: &Self::Parsed) -> $crate::anyhow::Result<Self::Out2> $part2
+            $crate::day!(@examples $($($examples)*)?);
+        }
+        $(impl $crate::StreamingSolution for $name {
+            $(#[$stream_meta])*
+            fn solve_reader(
+                $reader: impl ::std::io::BufRead,
+            ) -> $crate::anyhow::Result<(Self::Out1, Self::Out2)> $stream
+        })?
+        $($crate::test_examples!($name { $($examples)* });)?
+    };
+}
+
+/// Define `fn main` for a day's binary, running the solution with
+/// [`run_solution`], or [`run_streaming_solution`] when followed by `, streaming`.
+#[macro_export]
+macro_rules! main {
+    ($solution:ty) => {
+        fn main() -> $crate::anyhow::Result<()> {
+            $crate::run_solution::<$solution>()
+        }
+    };
+    ($solution:ty, streaming) => {
+        fn main() -> $crate::anyhow::Result<()> {
+            $crate::run_streaming_solution::<$solution>()
+        }
+    };
+}
+
 #[derive(Clone, Debug)]
 pub struct Grid<T> {
     data: Vec<T>,
@@ -0,0 +1,160 @@
+use std::fmt;
+
+/// Fixes applied to an input before it is solved, to undo what editors and other
+/// platforms do to saved files.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Normalize {
+    /// Remove a leading byte order mark.
+    pub bom: bool,
+    /// Convert `\r\n` line endings to `\n`.
+    pub crlf: bool,
+    /// Remove spaces and tabs from the end of each line.
+    pub trailing_spaces: bool,
+    /// End a non-empty input with exactly one `\n`.
+    pub trailing_newline: bool,
+}
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Self {
+            bom: true,
+            crlf: true,
+            trailing_spaces: true,
+            trailing_newline: true,
+        }
+    }
+}
+
+/// What [`Normalize::apply`] changed.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct InputChanges {
+    pub bom: bool,
+    /// The number of `\r\n` line endings converted.
+    pub crlf: usize,
+    /// The number of lines with trailing spaces removed.
+    pub trailing_spaces: usize,
+    pub trailing_newline: bool,
+}
+
+impl Normalize {
+    /// Leave the input exactly as it is.
+    pub const RAW: Self = Self {
+        bom: false,
+        crlf: false,
+        trailing_spaces: false,
+        trailing_newline: false,
+    };
+
+    pub fn apply(&self, input: &str) -> (String, InputChanges) {
+        let mut changes = InputChanges::default();
+        let mut input = input;
+        if self.bom {
+            if let Some(rest) = input.strip_prefix('\u{feff}') {
+                changes.bom = true;
+                input = rest;
+            }
+        }
+        let mut normalized = String::with_capacity(input.len() + 1);
+        for line in input.split_inclusive('\n') {
+            let (mut text, ending) = match line.strip_suffix('\n') {
+                Some(text) => (text, "\n"),
+                None => (line, ""),
+            };
+            if self.crlf && !ending.is_empty() {
+                if let Some(stripped) = text.strip_suffix('\r') {
+                    changes.crlf += 1;
+                    text = stripped;
+                }
+            }
+            if self.trailing_spaces {
+                let trimmed = text.trim_end_matches(&[' ', '\t'][..]);
+                changes.trailing_spaces += (trimmed.len() != text.len()) as usize;
+                text = trimmed;
+            }
+            normalized.push_str(text);
+            normalized.push_str(ending);
+        }
+        if self.trailing_newline && !normalized.trim_end_matches('\n').is_empty() {
+            let len = normalized.trim_end_matches('\n').len();
+            if normalized.len() != len + 1 {
+                changes.trailing_newline = true;
+                normalized.truncate(len);
+                normalized.push('\n');
+            }
+        }
+        (normalized, changes)
+    }
+}
+
+/// Normalize `input`, warning about any changes. `name` says which input it is.
+pub(crate) fn normalize_with_warning(normalize: &Normalize, input: String, name: &str) -> String {
+    let (normalized, changes) = normalize.apply(&input);
+    if changes.is_empty() {
+        return input;
+    }
+    eprintln!("WARN: normalized {}: {}", name, changes);
+    normalized
+}
+
+impl InputChanges {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for InputChanges {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut changes = vec![];
+        if self.bom {
+            changes.push("removed a byte order mark".to_owned());
+        }
+        if self.crlf > 0 {
+            changes.push(format!("converted {} CRLF line endings", self.crlf));
+        }
+        if self.trailing_spaces > 0 {
+            changes.push(format!(
+                "removed trailing spaces from {} lines",
+                self.trailing_spaces
+            ));
+        }
+        if self.trailing_newline {
+            changes.push("fixed the trailing newline".to_owned());
+        }
+        match changes.is_empty() {
+            true => write!(f, "no changes"),
+            false => write!(f, "{}", changes.join(", ")),
+        }
+    }
+}
+
+#[test]
+fn test_normalize() {
+    let (input, changes) = Normalize::default().apply("\u{feff}101 \r\n010\r\n\r\n\n");
+    assert_eq!(input, "101\n010\n");
+    assert_eq!(
+        changes,
+        InputChanges {
+            bom: true,
+            crlf: 3,
+            trailing_spaces: 1,
+            trailing_newline: true,
+        }
+    );
+    assert_eq!(
+        changes.to_string(),
+        "removed a byte order mark, converted 3 CRLF line endings, \
+         removed trailing spaces from 1 lines, fixed the trailing newline"
+    );
+
+    let (input, changes) = Normalize::default().apply("1\n2");
+    assert_eq!(input, "1\n2\n");
+    assert!(changes.trailing_newline);
+    assert!(Normalize::default().apply("1\n2\n").1.is_empty());
+    assert!(Normalize::default().apply("").1.is_empty());
+
+    let raw = "\u{feff}1 \r\n\n";
+    assert_eq!(
+        Normalize::RAW.apply(raw),
+        (raw.to_owned(), InputChanges::default())
+    );
+}
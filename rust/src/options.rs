@@ -0,0 +1,311 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    time::Duration,
+};
+
+use anyhow::{bail, ensure, Context, Result};
+
+use crate::{
+    normalize, trace, AnswerRegistry, BenchConfig, Format, InputCache, Normalize, Profile,
+};
+
+/// Where a run reads its input from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    /// The real input, from the cache or adventofcode.com.
+    Real,
+    /// The saved example input, `dayNN.example.txt` in the input directory.
+    Example,
+    /// A local file.
+    File(PathBuf),
+    /// Everything piped to stdin.
+    Stdin,
+}
+
+/// Command-line options accepted by [`run_solution`](crate::run_solution).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Options {
+    pub input: InputSource,
+    /// Solve the input file or stdin as it is read, instead of reading it all first.
+    pub stream: bool,
+    /// Only run this part (1 or 2), instead of both.
+    pub part: Option<u8>,
+    /// Download the real input even if it is cached.
+    pub refresh: bool,
+    /// Submit the answer for this part (1 or 2) after solving.
+    pub submit: Option<u8>,
+    /// Check the answers against the known answers.
+    pub verify: bool,
+    /// Record the answers as the known answers.
+    pub lock: bool,
+    /// Benchmark the solution after running it.
+    pub bench: Option<BenchConfig>,
+    /// How to print the results.
+    pub format: Format,
+    /// Fixes applied to the input after reading it.
+    pub normalize: Normalize,
+    /// Use this profile's cookie, input and known answers instead of the default ones.
+    pub profile: Option<Profile>,
+    /// Print the solver's traces up to this level, overriding `AOC_TRACE`.
+    pub trace: Option<trace::Level>,
+}
+
+const USAGE: &str = "\
+Options:
+  -i, --input <PATH>      Read the input from PATH instead of downloading it ('-' for stdin)
+  -                       Read the input from stdin
+  -e, --example           Read the input from the saved example, inputs/YYYY/dayNN.example.txt
+      --profile <NAME>    Use the session cookie, inputs and answers of profile NAME
+      --stream            Solve the --input file or stdin as it is read (days 1, 2, 5 and 10)
+      --raw               Solve the input exactly as read, without normalizing it
+  -t, --trace <LEVEL>     Print the solver's traces up to LEVEL: info, debug or trace
+  -f, --format <FORMAT>   Print results as text (default), json or csv
+  -p, --part <PART>       Only run PART (1 or 2)
+  -r, --refresh           Download the real input even if it is cached
+  -s, --submit <PART>     Submit the answer for PART (1 or 2) to adventofcode.com
+  -v, --verify            Check the answers against answers/YYYY/dayNN.toml
+      --lock              Record the answers in answers/YYYY/dayNN.toml
+  -b, --bench             Benchmark the solution, excluding the input download
+      --iterations <N>    Benchmark exactly N runs, instead of for a time budget
+      --bench-time <SECS> Benchmark for this long (default 3)
+      --warmup <SECS>     Run unmeasured for this long before benchmarking (default 0.5)
+  -h, --help              Print this message";
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            input: InputSource::Real,
+            stream: false,
+            part: None,
+            refresh: false,
+            submit: None,
+            verify: false,
+            lock: false,
+            bench: None,
+            format: Format::Text,
+            normalize: Normalize::default(),
+            profile: None,
+            trace: None,
+        }
+    }
+}
+
+impl Options {
+    /// Parse the options given to the current process.
+    ///
+    /// Prints the usage and exits if `--help` is given.
+    pub fn from_args() -> Result<Self> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        Self::parse(args).with_context(|| format!("invalid arguments\n\n{}", USAGE))
+    }
+    /// Parse options from a list of arguments, not including the program name.
+    pub fn parse<I, A>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<String>,
+    {
+        let mut options = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .with_context(|| format!("missing value after {}", arg))
+            };
+            match arg.as_str() {
+                "-" => options.set_input(InputSource::Stdin)?,
+                "-i" | "--input" => match value()? {
+                    path if path == "-" => options.set_input(InputSource::Stdin)?,
+                    path => options.set_input(InputSource::File(path.into()))?,
+                },
+                "-e" | "--example" => options.set_input(InputSource::Example)?,
+                "--stream" => options.stream = true,
+                "-r" | "--refresh" => options.refresh = true,
+                "-v" | "--verify" => options.verify = true,
+                "--lock" => options.lock = true,
+                "--raw" => options.normalize = Normalize::RAW,
+                "-t" | "--trace" => options.trace = Some(value()?.parse()?),
+                "--profile" => options.profile = Some(Profile::new(value()?)?),
+                "-f" | "--format" => options.format = value()?.parse()?,
+                "-p" | "--part" => options.part = Some(parse_part(&value()?)?),
+                "-s" | "--submit" => options.submit = Some(parse_part(&value()?)?),
+                "-b" | "--bench" => {
+                    options.bench.get_or_insert_with(BenchConfig::default);
+                }
+                "--iterations" => {
+                    let iterations = value()?;
+                    let iterations = iterations
+                        .parse()
+                        .with_context(|| format!("invalid iterations '{}'", iterations))?;
+                    ensure!(iterations > 0, "iterations must be at least 1");
+                    options
+                        .bench
+                        .get_or_insert_with(BenchConfig::default)
+                        .iterations = Some(iterations);
+                }
+                "--bench-time" => {
+                    let time = parse_secs(&value()?)?;
+                    options.bench.get_or_insert_with(BenchConfig::default).time = time;
+                }
+                "--warmup" => {
+                    let warmup = parse_secs(&value()?)?;
+                    options
+                        .bench
+                        .get_or_insert_with(BenchConfig::default)
+                        .warmup = warmup;
+                }
+                _ => bail!("unexpected argument '{}'", arg),
+            }
+        }
+        ensure!(
+            options.input == InputSource::Real || !(options.verify || options.lock),
+            "known answers only apply to the real input"
+        );
+        ensure!(
+            !options.stream || matches!(options.input, InputSource::File(_) | InputSource::Stdin),
+            "--stream needs --input"
+        );
+        ensure!(
+            !(options.stream && options.bench.is_some()),
+            "--bench needs the whole input, so it cannot be used with --stream"
+        );
+        ensure!(
+            options.format == Format::Text || (options.submit.is_none() && options.bench.is_none()),
+            "--submit and --bench only support text output"
+        );
+        Ok(options)
+    }
+    fn set_input(&mut self, input: InputSource) -> Result<()> {
+        ensure!(self.input == InputSource::Real, "more than one input given");
+        self.input = input;
+        Ok(())
+    }
+    /// Read the input for the given year and day from the configured source,
+    /// and normalize it as configured.
+    pub fn read_input(&self, year: u16, day: u8) -> Result<String> {
+        let input = self.read_raw_input(year, day)?;
+        let name = format!("input for {} day {:02}", year, day);
+        Ok(normalize::normalize_with_warning(
+            &self.normalize,
+            input,
+            &name,
+        ))
+    }
+    /// The input cache of the configured profile.
+    pub fn input_cache(&self) -> InputCache {
+        match &self.profile {
+            Some(profile) => profile.input_cache(),
+            None => InputCache::from_env(),
+        }
+    }
+    /// The known answers of the configured profile.
+    pub fn answers(&self) -> AnswerRegistry {
+        match &self.profile {
+            Some(profile) => profile.answers(),
+            None => AnswerRegistry::from_env(),
+        }
+    }
+    fn read_raw_input(&self, year: u16, day: u8) -> Result<String> {
+        match &self.input {
+            InputSource::Real => {
+                let cache = self.input_cache();
+                let refresh = self.refresh || cache.refresh();
+                cache.with_refresh(refresh).get(year, day)
+            }
+            InputSource::Example => {
+                let path = InputCache::from_env().example_path(year, day);
+                fs::read_to_string(&path)
+                    .with_context(|| format!("failed to read example {}", path.display()))
+            }
+            InputSource::File(path) => fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display())),
+            InputSource::Stdin => {
+                let mut data = String::new();
+                io::stdin()
+                    .read_to_string(&mut data)
+                    .context("failed to read stdin")?;
+                Ok(data)
+            }
+        }
+    }
+}
+
+fn parse_part(s: &str) -> Result<u8> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => bail!("invalid part '{}'", s),
+    }
+}
+
+fn parse_secs(s: &str) -> Result<Duration> {
+    let secs: f64 = s
+        .parse()
+        .with_context(|| format!("invalid number of seconds '{}'", s))?;
+    ensure!(
+        secs >= 0.0 && secs.is_finite(),
+        "invalid number of seconds '{}'",
+        s
+    );
+    // Rounded, the maximum itself is too large for a Duration.
+    ensure!(
+        secs < Duration::MAX.as_secs_f64(),
+        "too many seconds '{}'",
+        s
+    );
+    Ok(Duration::from_secs_f64(secs))
+}
+
+#[test]
+fn test_parse() {
+    let parse = |args: &[&str]| Options::parse(args.iter().copied());
+    assert_eq!(parse(&[]).unwrap(), Options::default());
+    assert_eq!(parse(&["-"]).unwrap().input, InputSource::Stdin);
+    assert_eq!(parse(&["--input", "-"]).unwrap().input, InputSource::Stdin);
+    assert_eq!(
+        parse(&["-i", "my.txt"]).unwrap().input,
+        InputSource::File("my.txt".into())
+    );
+    assert_eq!(parse(&["--example"]).unwrap().input, InputSource::Example);
+    assert!(parse(&["--refresh"]).unwrap().refresh);
+    assert_eq!(parse(&["--submit", "2"]).unwrap().submit, Some(2));
+    assert!(parse(&["--submit", "3"]).is_err());
+    assert_eq!(parse(&["--part", "1"]).unwrap().part, Some(1));
+    assert_eq!(parse(&["-f", "csv"]).unwrap().format, Format::Csv);
+    assert_eq!(parse(&["--raw"]).unwrap().normalize, Normalize::RAW);
+    assert!(parse(&["-f", "json", "-b"]).is_err());
+    assert_eq!(
+        parse(&["--profile", "bob"]).unwrap().profile,
+        Some(Profile::new("bob").unwrap())
+    );
+    assert!(parse(&["--profile", "a/b"]).is_err());
+    assert_eq!(
+        parse(&["-t", "debug"]).unwrap().trace,
+        Some(trace::Level::Debug)
+    );
+    assert!(parse(&["--verify", "--lock"]).unwrap().verify);
+    assert!(parse(&["--verify", "-e"]).is_err());
+    assert_eq!(parse(&["-b"]).unwrap().bench, Some(BenchConfig::default()));
+    let bench = parse(&["--iterations", "10", "--warmup", "0"])
+        .unwrap()
+        .bench
+        .unwrap();
+    assert_eq!(bench.iterations, Some(10));
+    assert_eq!(bench.warmup, Duration::ZERO);
+    assert!(parse(&["--bench-time", "x"]).is_err());
+    assert!(parse(&["--bench-time", "1e30"]).is_err());
+    assert!(parse(&["--warmup", "-1"]).is_err());
+    assert!(parse(&["--input"]).is_err());
+    assert!(parse(&["-e", "-"]).is_err());
+    assert!(parse(&["--bogus"]).is_err());
+    assert!(parse(&["-i", "big.txt", "--stream"]).unwrap().stream);
+    assert!(parse(&["--stream", "-"]).unwrap().stream);
+    assert!(parse(&["--stream"]).is_err());
+    assert!(parse(&["--stream", "-e"]).is_err());
+    assert!(parse(&["--stream", "-i", "big.txt", "-b"]).is_err());
+}
@@ -0,0 +1,134 @@
+use std::{
+    error, fmt,
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+/// An error in the puzzle input, pointing at where it went wrong.
+///
+/// Displayed as e.g. `line 17, col 5: expected ',' in "3,4 -> 1"`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, in chars
+    pub col: usize,
+    /// The whole offending line
+    pub text: String,
+    /// What was expected at the column, e.g. `','` or `a number`
+    pub expected: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, col {}: expected {} in {:?}",
+            self.line, self.col, self.expected, self.text
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// A line of puzzle input, which knows its line number.
+///
+/// The methods take `part`, a subslice of the line, so that errors can report the column.
+#[derive(Copy, Clone, Debug)]
+pub struct Line<'a> {
+    /// 1-based line number
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// The lines of `input`, numbered from 1.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+/// Reads numbered lines from a [`BufRead`] into one reused buffer, so that inputs
+/// too large to hold in memory can be processed line by line.
+#[derive(Debug)]
+pub struct LineReader<R> {
+    reader: R,
+    buffer: String,
+    number: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+            number: 0,
+        }
+    }
+    /// The next line without its line ending, like [`str::lines`], or `None` at the end.
+    pub fn next_line(&mut self) -> io::Result<Option<Line<'_>>> {
+        self.buffer.clear();
+        if self.reader.read_line(&mut self.buffer)? == 0 {
+            return Ok(None);
+        }
+        self.number += 1;
+        let text = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        Ok(Some(Line {
+            number: self.number,
+            text,
+        }))
+    }
+}
+
+impl<'a> Line<'a> {
+    /// An error at the start of `at`, which should be a subslice of this line.
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.number,
+            col: self.column(at),
+            text: self.text.to_owned(),
+            expected: expected.into(),
+        }
+    }
+    /// Split `part` around the first `delimiter`.
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(delimiter)
+            .ok_or_else(|| self.error(part, format!("'{}'", delimiter)))
+    }
+    /// Parse `part`, which should look like `expected`.
+    pub fn parse<T: FromStr>(&self, part: &'a str, expected: &str) -> Result<T, ParseError> {
+        part.parse().map_err(|_| self.error(part, expected))
+    }
+    fn column(&self, at: &str) -> usize {
+        (at.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .and_then(|offset| self.text.get(..offset))
+            .map_or(1, |before| before.chars().count() + 1)
+    }
+}
+
+#[test]
+fn test_parse_error() {
+    let input = "1,2 -> 3,4\n3,4 -> 1";
+    let line = numbered_lines(input).nth(1).unwrap();
+    let (start, end) = line.split_once(line.text, " -> ").unwrap();
+    assert_eq!(line.parse::<u8>(start, "a number").unwrap_err().col, 1);
+    assert_eq!(
+        line.split_once(end, ",").unwrap_err().to_string(),
+        r#"line 2, col 8: expected ',' in "3,4 -> 1""#
+    );
+    assert_eq!(line.error("elsewhere", "x").col, 1);
+
+    let mut reader = LineReader::new("a\r\n\nb".as_bytes());
+    let mut lines = vec![];
+    while let Some(line) = reader.next_line().unwrap() {
+        lines.push((line.number, line.text.to_owned()));
+    }
+    assert_eq!(lines, [(1, "a".into()), (2, "".into()), (3, "b".into())]);
+}
@@ -0,0 +1,112 @@
+use anyhow::{bail, ensure, Result};
+
+use crate::{input, AnswerRegistry, InputCache};
+
+/// A named session on adventofcode.com, such as a teammate's account.
+///
+/// Each profile has its own input, so it has its own session cookie, in
+/// `AOC_SESSION_COOKIE_{NAME}`, and its own input cache and known answers, in
+/// `{dir}/profiles/{name}/` under the usual directories.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Profile {
+    pub name: String,
+}
+
+impl Profile {
+    /// Names may only use ASCII letters, digits, `-` and `_`, as they are used in paths
+    /// and environment variable names.
+    pub fn new(name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        ensure!(
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            "invalid profile name '{}'",
+            name
+        );
+        Ok(Self { name })
+    }
+    /// The profiles listed in `AOC_PROFILES` (which may be set in a `.env` file),
+    /// separated by commas.
+    pub fn from_env() -> Result<Vec<Self>> {
+        input::load_dotenv();
+        Self::parse_list(&std::env::var("AOC_PROFILES").unwrap_or_default())
+    }
+    /// Profiles separated by commas. Two names with the same cookie variable, such as
+    /// `team-b` and `TEAM_B`, are an error, since they would share one session.
+    pub fn parse_list(names: &str) -> Result<Vec<Self>> {
+        let profiles = names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(Self::new)
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(
+            !profiles.is_empty(),
+            "no profiles are listed in AOC_PROFILES"
+        );
+        for (i, profile) in profiles.iter().enumerate() {
+            let var = profile.cookie_var();
+            if let Some(other) = profiles[..i].iter().find(|p| p.cookie_var() == var) {
+                bail!(
+                    "profiles '{}' and '{}' would both use {}",
+                    other.name,
+                    profile.name,
+                    var
+                );
+            }
+        }
+        Ok(profiles)
+    }
+    /// The environment variable holding this profile's session cookie.
+    pub fn cookie_var(&self) -> String {
+        format!(
+            "{}_{}",
+            input::SESSION_COOKIE_VAR,
+            self.name.to_ascii_uppercase().replace('-', "_")
+        )
+    }
+    /// The `Cookie` header value for this profile.
+    pub fn cookie(&self) -> Result<String> {
+        input::load_dotenv();
+        input::session_cookie_from(&self.cookie_var())
+    }
+    /// This profile's input cache, configured like [`InputCache::from_env`].
+    pub fn input_cache(&self) -> InputCache {
+        let cache = InputCache::from_env();
+        InputCache::new(cache.dir().join("profiles").join(&self.name))
+            .with_refresh(cache.refresh())
+            .with_cookie_var(self.cookie_var())
+    }
+    /// This profile's known answers, configured like [`AnswerRegistry::from_env`].
+    pub fn answers(&self) -> AnswerRegistry {
+        let registry = AnswerRegistry::from_env();
+        AnswerRegistry::new(registry.dir().join("profiles").join(&self.name))
+    }
+}
+
+#[test]
+fn test_profile() {
+    let profile = Profile::new("team-b_2").unwrap();
+    assert_eq!(profile.cookie_var(), "AOC_SESSION_COOKIE_TEAM_B_2");
+    assert!(profile
+        .input_cache()
+        .path(2021, 7)
+        .ends_with("profiles/team-b_2/2021/day07.txt"));
+    assert!(Profile::new("").is_err());
+    assert!(Profile::new("../x").is_err());
+
+    let names = |list| -> Result<Vec<String>> {
+        Ok(Profile::parse_list(list)?
+            .into_iter()
+            .map(|p| p.name)
+            .collect())
+    };
+    assert_eq!(names(" a, team-b ,").unwrap(), vec!["a", "team-b"]);
+    assert!(names("").is_err());
+    assert!(names("a,a").is_err());
+    let error = names("team-b,team_b").unwrap_err().to_string();
+    assert!(error.contains("AOC_SESSION_COOKIE_TEAM_B"), "{}", error);
+    assert!(names("bob,Bob").is_err());
+}
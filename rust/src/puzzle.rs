@@ -0,0 +1,413 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+
+use crate::input;
+
+/// The examples and highlighted answers of a puzzle description page.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PuzzlePage {
+    /// One per `<article>`: part 1, then part 2 once it is unlocked.
+    pub parts: Vec<PuzzlePart>,
+}
+
+/// One part of a puzzle description.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PuzzlePart {
+    /// The text of every `<pre><code>` block, in order, with tags removed.
+    pub examples: Vec<String>,
+    /// The text of every highlighted `<code><em>` outside of examples, in order.
+    pub answers: Vec<String>,
+}
+
+impl PuzzlePage {
+    pub fn parse(html: &str) -> Self {
+        let parts = html
+            .split("<article")
+            .skip(1)
+            .map(|article| {
+                let article = article.split("</article>").next().unwrap_or(article);
+                PuzzlePart::parse(article)
+            })
+            .collect();
+        Self { parts }
+    }
+    /// Parse a saved puzzle page.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let html = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Ok(Self::parse(&html))
+    }
+    /// The part with the given number (1 or 2), if the page has it.
+    pub fn part(&self, level: u8) -> Option<&PuzzlePart> {
+        self.parts.get((level as usize).checked_sub(1)?)
+    }
+}
+
+impl PuzzlePart {
+    fn parse(article: &str) -> Self {
+        let mut part = Self::default();
+        let mut rest = article;
+        while let Some((before, pre)) = rest.split_once("<pre><code>") {
+            part.answers.extend(highlighted(before));
+            let (code, after) = pre.split_once("</code></pre>").unwrap_or((pre, ""));
+            part.examples.push(text(code));
+            rest = after;
+        }
+        part.answers.extend(highlighted(rest));
+        part
+    }
+    /// The example most likely used for the answers: the first one.
+    pub fn example(&self) -> Option<&str> {
+        self.examples.first().map(String::as_str)
+    }
+    /// The answer for the example, which is usually the last highlighted value.
+    pub fn answer(&self) -> Option<&str> {
+        self.answers.last().map(String::as_str)
+    }
+}
+
+/// Download the description page for the given day.
+///
+/// The session cookie is sent if it is set, so part 2 is included once it is unlocked.
+pub fn download_puzzle(year: u16, day: u8) -> Result<String> {
+    input::load_dotenv();
+    download_puzzle_with(input::session_cookie().ok().as_deref(), year, day)
+}
+
+/// Download the description page for the given day, with `cookie` as the `Cookie` header.
+pub fn download_puzzle_with(cookie: Option<&str>, year: u16, day: u8) -> Result<String> {
+    let url = format!("{}/{}/day/{}", input::base_url(), year, day);
+    let mut request = ureq::get(&url);
+    if let Some(cookie) = cookie {
+        request = request.set("Cookie", cookie);
+    }
+    request
+        .call()
+        .context("http request error")?
+        .into_string()
+        .context("http response error")
+}
+
+/// Render the descriptions on a puzzle page as text for a terminal, each followed by its
+/// accepted answer if the page has one.
+///
+/// Paragraphs and lists are wrapped to 80 columns, and code blocks are indented. Inline
+/// code is shown as `` `code` `` and emphasis as `*em*`, or with `ansi`, in color and bold.
+pub fn render_puzzle(html: &str, ansi: bool) -> String {
+    let mut renderer = Renderer::new(ansi);
+    for article in html.split("<article").skip(1) {
+        let (article, after) = article.split_once("</article>").unwrap_or((article, ""));
+        renderer.render(article.split_once('>').map_or(article, |(_, inner)| inner));
+        let after = after.split("<article").next().unwrap_or_default();
+        if let Some((_, answer)) = after.split_once("<p>Your puzzle answer was") {
+            let answer = answer.split("</p>").next().unwrap_or_default();
+            renderer.render(&format!("<p>Your puzzle answer was{}</p>", answer));
+        }
+    }
+    renderer.out.trim_end().to_owned() + "\n"
+}
+
+const WIDTH: usize = 80;
+
+struct Renderer {
+    ansi: bool,
+    out: String,
+    /// The words of the paragraph or list item being written, with their widths.
+    words: Vec<(String, usize)>,
+    word: (String, usize),
+    /// The indent of the first line, and of the others, for list items.
+    indent: (&'static str, &'static str),
+    pre: bool,
+    em: bool,
+    code: bool,
+    heading: bool,
+}
+
+impl Renderer {
+    fn new(ansi: bool) -> Self {
+        Self {
+            ansi,
+            out: String::new(),
+            words: vec![],
+            word: (String::new(), 0),
+            indent: ("", ""),
+            pre: false,
+            em: false,
+            code: false,
+            heading: false,
+        }
+    }
+    fn render(&mut self, html: &str) {
+        let mut rest = html;
+        while !rest.is_empty() {
+            let (text, tag) = match rest.find('<') {
+                Some(0) => match rest.split_once('>') {
+                    Some((tag, after)) => {
+                        rest = after;
+                        ("", &tag[1..])
+                    }
+                    None => ("", std::mem::take(&mut rest)),
+                },
+                Some(start) => {
+                    let text = &rest[..start];
+                    rest = &rest[start..];
+                    (text, "")
+                }
+                None => ("", std::mem::take(&mut rest)),
+            };
+            self.text(&decode(text));
+            if !tag.is_empty() {
+                self.tag(tag);
+            }
+        }
+        self.flush();
+    }
+    fn tag(&mut self, tag: &str) {
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        match name {
+            "h2" => {
+                self.flush();
+                self.heading = true;
+                self.restyle();
+            }
+            "/h2" => {
+                self.heading = false;
+                self.restyle();
+                self.flush();
+            }
+            "p" | "/p" | "ul" | "/li" => self.flush(),
+            "/ul" => {
+                self.flush();
+                self.out.push('\n');
+            }
+            "li" => {
+                self.flush();
+                self.indent = ("  - ", "    ");
+            }
+            "pre" => {
+                self.flush();
+                self.pre = true;
+            }
+            "/pre" => {
+                self.pre = false;
+                let block = std::mem::take(&mut self.word.0);
+                for line in block.trim_end_matches('\n').lines() {
+                    match (self.ansi, line.is_empty()) {
+                        (_, true) => self.out.push('\n'),
+                        (true, false) => {
+                            self.out.push_str(&format!("    \x1b[36m{}\x1b[0m\n", line))
+                        }
+                        (false, false) => self.out.push_str(&format!("    {}\n", line)),
+                    }
+                }
+                self.out.push('\n');
+            }
+            "em" if self.pre && self.ansi => self.word.0.push_str("\x1b[1m"),
+            "/em" if self.pre && self.ansi => self.word.0.push_str("\x1b[22m"),
+            _ if self.pre => {}
+            "em" | "/em" => {
+                self.em = name == "em";
+                self.style_change("*");
+            }
+            "code" | "/code" => {
+                self.code = name == "code";
+                self.style_change("`");
+            }
+            _ => {}
+        }
+    }
+    fn text(&mut self, text: &str) {
+        if self.pre {
+            self.word.0.push_str(text);
+            return;
+        }
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.end_word();
+            } else {
+                let mut buf = [0; 4];
+                self.push_str(c.encode_utf8(&mut buf), 1);
+            }
+        }
+    }
+    /// Mark the start or end of inline code or emphasis.
+    fn style_change(&mut self, marker: &str) {
+        match self.ansi {
+            true => self.restyle(),
+            false => self.push_str(marker, marker.len()),
+        }
+    }
+    /// Switch to the current style, with ANSI escapes.
+    fn restyle(&mut self) {
+        if self.ansi {
+            let mut escape = String::from("\x1b[0m");
+            if self.em || self.heading {
+                escape.push_str("\x1b[1m");
+            }
+            if self.code {
+                escape.push_str("\x1b[36m");
+            }
+            self.push_str(&escape, 0);
+        }
+    }
+    fn push_str(&mut self, s: &str, width: usize) {
+        self.word.0.push_str(s);
+        self.word.1 += width;
+    }
+    /// End the current word, unless it is only escapes, which are kept for the next.
+    fn end_word(&mut self) {
+        if self.word.1 > 0 {
+            self.words.push(std::mem::take(&mut self.word));
+        }
+    }
+    /// Write the paragraph or list item being written, wrapped.
+    fn flush(&mut self) {
+        self.end_word();
+        if let Some(last) = self.words.last_mut() {
+            last.0.push_str(&std::mem::take(&mut self.word.0));
+        }
+        if self.words.is_empty() {
+            return;
+        }
+        let (first, hanging) = std::mem::take(&mut self.indent);
+        self.out.push_str(first);
+        let mut width = first.len();
+        for (i, (word, len)) in std::mem::take(&mut self.words).into_iter().enumerate() {
+            if i > 0 && width + 1 + len > WIDTH {
+                self.out.push('\n');
+                self.out.push_str(hanging);
+                width = hanging.len();
+            } else if i > 0 {
+                self.out.push(' ');
+                width += 1;
+            }
+            self.out.push_str(&word);
+            width += len;
+        }
+        // List items are not separated, only the list.
+        match first {
+            "" => self.out.push_str("\n\n"),
+            _ => self.out.push('\n'),
+        }
+    }
+}
+
+/// The highlighted values, `<code><em>..</em></code>` or `<em><code>..</code></em>`.
+fn highlighted(html: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut rest = html;
+    let tags = [
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ];
+    while let Some((start, open, close)) = tags
+        .iter()
+        .filter_map(|&(open, close)| Some((rest.find(open)?, open, close)))
+        .min_by_key(|&(start, ..)| start)
+    {
+        let inner = &rest[start + open.len()..];
+        let (value, after) = inner.split_once(close).unwrap_or((inner, ""));
+        values.push(text(value));
+        rest = after;
+    }
+    values
+}
+
+/// Remove tags and decode entities.
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode(&text)
+}
+
+/// Decode the entities used by the site.
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[test]
+fn test_parse_page() {
+    let html = r#"<main>
+<article class="day-desc"><h2>--- Day 5: Test ---</h2>
+<p>For example:</p>
+<pre><code>0,9 -&gt; 5,9
+<em>8,0</em> -&gt; 0,8
+</code></pre>
+<p>In this example, the answer is <code><em>5</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>6005</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Consider <code>&lt;x&gt;</code>; now <em><code>12</code></em> points overlap, or <code><em>13</em></code>.</p>
+</article>
+</main>"#;
+    let page = PuzzlePage::parse(html);
+    assert_eq!(page.parts.len(), 2);
+    let part1 = page.part(1).unwrap();
+    assert_eq!(part1.example(), Some("0,9 -> 5,9\n8,0 -> 0,8\n"));
+    assert_eq!(part1.answers, ["5"]);
+    let part2 = page.part(2).unwrap();
+    assert!(part2.examples.is_empty());
+    assert_eq!(part2.answers, ["12", "13"]);
+    assert_eq!(part2.answer(), Some("13"));
+    assert!(page.part(3).is_none());
+}
+
+#[test]
+fn test_render_puzzle() {
+    let html = r#"<main>
+<article class="day-desc"><h2>--- Day 5: Test ---</h2>
+<p>You come across a field of <a href="https://example.com">hydrothermal vents</a> on the
+ocean floor! These vents constantly produce large, <span title="!">opaque</span> clouds.</p>
+<ul>
+<li>An entry like <code>1,1 -&gt; 1,3</code> covers points <code>1,1</code>, <code>1,2</code>, and <code>1,3</code>.</li>
+<li>Short.</li>
+</ul>
+<pre><code>0,9 -&gt; 5,9
+<em>8,0</em> -&gt; 0,8
+</code></pre>
+<p>In this example, the answer is <code><em>5</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>6005</code>.</p><p>The first half of this puzzle is complete!</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Now <em>how many</em> overlap?</p>
+</article>
+</main>"#;
+    let text = "\
+--- Day 5: Test ---
+
+You come across a field of hydrothermal vents on the ocean floor! These vents
+constantly produce large, opaque clouds.
+
+  - An entry like `1,1 -> 1,3` covers points `1,1`, `1,2`, and `1,3`.
+  - Short.
+
+    0,9 -> 5,9
+    8,0 -> 0,8
+
+In this example, the answer is `*5*`.
+
+Your puzzle answer was `6005`.
+
+--- Part Two ---
+
+Now *how many* overlap?
+";
+    assert_eq!(render_puzzle(html, false), text);
+    let ansi = render_puzzle(html, true);
+    assert!(ansi.contains("    \x1b[36m\x1b[1m8,0\x1b[22m -> 0,8\x1b[0m\n"));
+    assert!(ansi.contains("\x1b[1mhow many\x1b[0m overlap?"));
+}
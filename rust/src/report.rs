@@ -0,0 +1,216 @@
+use std::{str::FromStr, time::Duration};
+
+use anyhow::{bail, Error};
+
+use crate::{AllocStats, PhaseTimings, Verdict};
+
+/// How results are printed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// Human-oriented text.
+    Text,
+    /// One JSON object per line, per day.
+    Json,
+    /// A header line, then one CSV row per day.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => bail!("invalid format '{}', expected text, json or csv", s),
+        }
+    }
+}
+
+/// The build profile, as printed with timings.
+pub fn profile() -> &'static str {
+    if cfg!(debug_assertions) {
+        "DEBUG"
+    } else {
+        "RELEASE"
+    }
+}
+
+/// Everything about running one day, for machine-readable output.
+#[derive(Clone, Debug, Default)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    /// The answers, as they would be printed. `None` if a part was not run or failed.
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    /// `None` if solving failed.
+    pub timings: Option<PhaseTimings>,
+    /// The release-mode time budget of the solution, when it was run on the real input.
+    pub budget: Option<Duration>,
+    /// The allocations made while solving, if the
+    /// [`CountingAllocator`](crate::CountingAllocator) is installed.
+    pub allocations: Option<AllocStats>,
+    /// The result of checking each part against the known answers, if requested.
+    pub verdicts: [Option<Verdict>; 2],
+    /// The full error chain, if retrieving the input or solving failed.
+    pub error: Option<String>,
+}
+
+impl Record {
+    pub const CSV_HEADER: &'static str =
+        "year,day,part1,part2,total_ns,parse_ns,part1_ns,part2_ns,budget_ns,\
+         alloc_count,alloc_bytes,alloc_peak,profile,part1_verdict,part2_verdict,error";
+
+    pub fn new(year: u16, day: u8) -> Self {
+        Self {
+            year,
+            day,
+            ..Default::default()
+        }
+    }
+    /// Whether the day failed to solve, an answer did not match the known answer, or a
+    /// release build took longer than the budget.
+    pub fn failed(&self) -> bool {
+        self.error.is_some()
+            || self.verdicts.iter().flatten().any(Verdict::is_fail)
+            || (self.over_budget() && !cfg!(debug_assertions))
+    }
+    /// Whether solving took longer than the budget. Only release builds are expected to
+    /// meet it.
+    pub fn over_budget(&self) -> bool {
+        match (self.timings, self.budget) {
+            (Some(timings), Some(budget)) => timings.total > budget,
+            _ => false,
+        }
+    }
+    fn durations(&self) -> [Option<Duration>; 4] {
+        match self.timings {
+            Some(t) => [Some(t.total), t.parse, t.part1, t.part2],
+            None => [None; 4],
+        }
+    }
+    /// A single-line JSON object.
+    pub fn to_json(&self) -> String {
+        let string = |s: &Option<String>| s.as_deref().map_or("null".into(), json_string);
+        let nanos = |d: Option<Duration>| d.map_or("null".into(), |d| d.as_nanos().to_string());
+        let verdict = |v: &Option<Verdict>| {
+            v.as_ref()
+                .map_or("null".into(), |v| json_string(verdict_label(v)))
+        };
+        let [total, parse, part1, part2] = self.durations();
+        format!(
+            "{{\"year\":{},\"day\":{},\"part1\":{},\"part2\":{},\"timings_ns\":{{\"total\":{},\"parse\":{},\"part1\":{},\"part2\":{},\"budget\":{}}},\"allocations\":{},\"profile\":\"{}\",\"verify\":{{\"part1\":{},\"part2\":{}}},\"error\":{}}}",
+            self.year,
+            self.day,
+            string(&self.part1),
+            string(&self.part2),
+            nanos(total),
+            nanos(parse),
+            nanos(part1),
+            nanos(part2),
+            nanos(self.budget),
+            self.allocations.map_or("null".into(), |a| format!(
+                "{{\"count\":{},\"bytes\":{},\"peak\":{}}}",
+                a.count, a.bytes, a.peak
+            )),
+            profile(),
+            verdict(&self.verdicts[0]),
+            verdict(&self.verdicts[1]),
+            string(&self.error),
+        )
+    }
+    /// A CSV row matching [`Record::CSV_HEADER`].
+    pub fn to_csv(&self) -> String {
+        let string = |s: &Option<String>| s.as_deref().map_or(String::new(), csv_field);
+        let nanos = |d: Option<Duration>| d.map_or(String::new(), |d| d.as_nanos().to_string());
+        let verdict = |v: &Option<Verdict>| v.as_ref().map_or("", verdict_label);
+        let allocations = |f: fn(AllocStats) -> String| self.allocations.map_or(String::new(), f);
+        let [total, parse, part1, part2] = self.durations();
+        [
+            self.year.to_string(),
+            self.day.to_string(),
+            string(&self.part1),
+            string(&self.part2),
+            nanos(total),
+            nanos(parse),
+            nanos(part1),
+            nanos(part2),
+            nanos(self.budget),
+            allocations(|a| a.count.to_string()),
+            allocations(|a| a.bytes.to_string()),
+            allocations(|a| a.peak.to_string()),
+            profile().to_owned(),
+            verdict(&self.verdicts[0]).to_owned(),
+            verdict(&self.verdicts[1]).to_owned(),
+            string(&self.error),
+        ]
+        .join(",")
+    }
+}
+
+fn verdict_label(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "PASS",
+        Verdict::Fail { .. } => "FAIL",
+        Verdict::Unknown => "UNKNOWN",
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[test]
+fn test_record_output() {
+    let mut record = Record::new(2021, 13);
+    record.part1 = Some("17".into());
+    record.part2 = Some("█ \"█\",\n█".into());
+    record.timings = Some(PhaseTimings {
+        total: Duration::from_nanos(1500),
+        ..Default::default()
+    });
+    record.verdicts = [Some(Verdict::Pass), None];
+    assert_eq!(
+        record.to_json(),
+        format!(
+            r#"{{"year":2021,"day":13,"part1":"17","part2":"█ \"█\",\n█","timings_ns":{{"total":1500,"parse":null,"part1":null,"part2":null,"budget":null}},"allocations":null,"profile":"{}","verify":{{"part1":"PASS","part2":null}},"error":null}}"#,
+            profile()
+        )
+    );
+    assert_eq!(
+        record.to_csv(),
+        format!(
+            "2021,13,17,\"█ \"\"█\"\",\n█\",1500,,,,,,,,{},PASS,,",
+            profile()
+        )
+    );
+    assert!(!record.failed());
+    record.budget = Some(Duration::from_nanos(1000));
+    assert!(record.over_budget());
+    assert_eq!(record.failed(), !cfg!(debug_assertions));
+    record.error = Some("oops".into());
+    assert!(record.failed());
+}
@@ -0,0 +1,370 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, ensure, Context, Result};
+
+use crate::{InputCache, PuzzlePage, DEFAULT_YEAR};
+
+/// Generates the files for a new day, and registers it in `src/days/mod.rs`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Scaffold {
+    pub day: u8,
+    /// The types of the answers to each part.
+    pub out: [String; 2],
+    /// The example input, for an example test.
+    pub example: Option<String>,
+    /// The example's answers; a part without one is not checked.
+    pub answers: [Option<String>; 2],
+}
+
+impl Scaffold {
+    pub fn new(day: u8) -> Self {
+        Self {
+            day,
+            out: ["usize".into(), "usize".into()],
+            example: None,
+            answers: [None, None],
+        }
+    }
+    /// Take the example and answers from a puzzle page, see [`PuzzlePart`](crate::PuzzlePart).
+    pub fn with_page(self, page: &PuzzlePage) -> Self {
+        let part1 = page.part(1);
+        let answer = |level| Some(page.part(level)?.answer()?.to_owned());
+        Self {
+            example: part1.and_then(|part| part.example()).map(Into::into),
+            answers: [answer(1), answer(2)],
+            ..self
+        }
+    }
+    /// The source of `src/days/dayN.rs`.
+    pub fn day_source(&self) -> String {
+        let mut source = format!(
+            "use anyhow::bail;\n\ncrate::day! {{\n    Day{day} = day {day} -> ({}, {});\n",
+            self.out[0],
+            self.out[1],
+            day = self.day
+        );
+        if let Some(example) = &self.example {
+            let answer = |answer: &Option<String>| match answer.as_deref() {
+                None => "_".to_owned(),
+                Some(answer) if answer.parse::<i64>().map_or(false, |n| n < 0) => {
+                    format!("({})", answer)
+                }
+                Some(answer) if answer.parse::<u64>().is_ok() => answer.to_owned(),
+                Some(answer) => format!("{:?}", answer),
+            };
+            source.push_str(&format!(
+                "    examples {{\n        example: {} => ({}, {}),\n    }}\n",
+                raw_string(example),
+                answer(&self.answers[0]),
+                answer(&self.answers[1])
+            ));
+        }
+        source.push_str(
+            "    fn solve(input) {\n        let _ = input;\n        bail!(\"not solved yet\")\n    }\n}\n",
+        );
+        source
+    }
+    /// The source of `src/bin/dayN.rs`.
+    pub fn bin_source(&self) -> String {
+        format!("aoc2021::main!(aoc2021::days::Day{});\n", self.day)
+    }
+    /// Add the day to the source of `src/days/mod.rs`: its module, its re-export, and
+    /// its entry in `all()`, in the order rustfmt would put them.
+    pub fn register(&self, mod_rs: &str) -> Result<String> {
+        let module = format!("day{}", self.day);
+        let lines: Vec<&str> = mod_rs.lines().collect();
+        ensure!(
+            !lines.contains(&format!("mod {};", module).as_str()),
+            "day {} is already registered",
+            self.day
+        );
+
+        let mut out: Vec<String> = vec![];
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            if line.starts_with("mod day") {
+                let mut modules = vec![format!("mod {};", module)];
+                while i < lines.len() && lines[i].starts_with("mod day") {
+                    modules.push(lines[i].to_owned());
+                    i += 1;
+                }
+                // Like rustfmt, by name: `day1` before `day10`.
+                modules.sort_by(|a, b| a.trim_end_matches(';').cmp(b.trim_end_matches(';')));
+                out.extend(modules);
+                continue;
+            }
+            if line == "pub use self::{" {
+                let mut items = vec![format!("{}::Day{}", module, self.day)];
+                i += 1;
+                while i < lines.len() && lines[i] != "};" {
+                    items.extend(
+                        lines[i]
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|s| !s.is_empty())
+                            .map(String::from),
+                    );
+                    i += 1;
+                }
+                items.sort_by(|a, b| a.split("::").cmp(b.split("::")));
+                out.push(line.to_owned());
+                out.extend(fill(&items, "    ", 100));
+                out.push("};".to_owned());
+                i += 1;
+                continue;
+            }
+            out.push(line.to_owned());
+            i += 1;
+        }
+
+        // In `all()`, before the first later day, or after the last day. A day may be
+        // added with any constructor, such as `Day::streaming::<Day1>()`.
+        let entry = |line: &str| -> Option<u8> {
+            let (_, day) = line.trim().strip_prefix("Day::")?.split_once("::<Day")?;
+            day.strip_suffix(">(),")?.parse().ok()
+        };
+        let entries: Vec<usize> = (0..out.len())
+            .filter(|&i| entry(&out[i]).is_some())
+            .collect();
+        let at = match entries.iter().find(|&&i| entry(&out[i]) > Some(self.day)) {
+            Some(&at) => at,
+            None => match entries.last() {
+                Some(&last) => last + 1,
+                None => bail!("found no days in all()"),
+            },
+        };
+        out.insert(at, format!("        Day::of::<Day{}>(),", self.day));
+
+        let mut source = out.join("\n");
+        source.push('\n');
+        Ok(source)
+    }
+    /// Write the new files in the crate at `root`, register the day, and save the example
+    /// in `inputs`. Existing files are never overwritten, and if any write fails, those
+    /// already written are undone.
+    ///
+    /// Returns the paths which were written.
+    pub fn write(&self, root: &Path, inputs: &InputCache) -> Result<Vec<PathBuf>> {
+        let day_path = root.join(format!("src/days/day{}.rs", self.day));
+        let bin_path = root.join(format!("src/bin/day{}.rs", self.day));
+        let mod_path = root.join("src/days/mod.rs");
+        for path in [&day_path, &bin_path] {
+            ensure!(!path.exists(), "{} already exists", path.display());
+        }
+        let mod_rs = fs::read_to_string(&mod_path)
+            .with_context(|| format!("failed to read {}", mod_path.display()))?;
+
+        let mut files = vec![(day_path, self.day_source()), (bin_path, self.bin_source())];
+        if let Some(example) = &self.example {
+            let path = inputs.example_path(DEFAULT_YEAR, self.day);
+            if !path.exists() {
+                files.push((path, example.clone()));
+            }
+        }
+        // Last, so the day is only registered once its files exist.
+        files.push((mod_path.clone(), self.register(&mod_rs)?));
+
+        let mut written = vec![];
+        for (path, contents) in files {
+            if let Err(e) = write_file(&path, &contents) {
+                for path in &written {
+                    let _ = fs::remove_file(path);
+                }
+                if path == mod_path {
+                    let _ = fs::write(&mod_path, &mod_rs);
+                }
+                return Err(e);
+            }
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// A raw string literal of `s`, with enough `#`s.
+fn raw_string(s: &str) -> String {
+    let hashes = (0..)
+        .find(|&n| !s.contains(&format!("\"{}", "#".repeat(n))))
+        .unwrap_or_default()
+        .max(1);
+    let hashes = "#".repeat(hashes);
+    format!("r{0}\"{1}\"{0}", hashes, s)
+}
+
+/// Lay out `items` separated by commas, in lines no longer than `width`.
+fn fill(items: &[String], indent: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::from(indent);
+    for item in items {
+        if line.len() > indent.len() && line.len() + 1 + item.len() + 1 > width {
+            lines.push(line.trim_end().to_owned());
+            line = String::from(indent);
+        }
+        line.push_str(item);
+        line.push_str(", ");
+    }
+    if line.len() > indent.len() {
+        lines.push(line.trim_end().to_owned());
+    }
+    lines
+}
+
+/// `src/days/mod.rs` as rustfmt lays it out, with days 1 to 14.
+#[cfg(test)]
+const MOD_RS: &str = "\
+use crate::Solution;
+
+mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+
+pub use self::{
+    day1::Day1, day10::Day10, day11::Day11, day12::Day12, day13::Day13, day14::Day14, day2::Day2,
+    day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8, day9::Day9,
+};
+
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::streaming::<Day1>(),
+        Day::streaming::<Day2>(),
+        Day::of::<Day3>(),
+        Day::of::<Day4>(),
+        Day::streaming::<Day5>(),
+        Day::of::<Day6>(),
+        Day::of::<Day7>(),
+        Day::of::<Day8>(),
+        Day::of::<Day9>(),
+        Day::streaming::<Day10>(),
+        Day::of::<Day11>(),
+        Day::of::<Day12>(),
+        Day::of::<Day13>(),
+        Day::of::<Day14>(),
+    ]
+}
+";
+
+#[test]
+fn test_scaffold() {
+    let scaffold = Scaffold {
+        day: 15,
+        out: ["u64".into(), "String".into()],
+        example: Some("1163\n\"#x\n".into()),
+        answers: [Some("40".into()), None],
+    };
+    let registered = scaffold.register(MOD_RS).unwrap();
+    let expected = MOD_RS
+        .replace("mod day14;\n", "mod day14;\nmod day15;\n")
+        .replace(
+            "day14::Day14, day2::Day2,\n    day3::Day3,",
+            "day14::Day14, day15::Day15,\n    day2::Day2, day3::Day3,",
+        )
+        .replace(
+            "Day::of::<Day14>(),\n",
+            "Day::of::<Day14>(),\n        Day::of::<Day15>(),\n",
+        );
+    assert_eq!(registered, expected);
+    assert!(scaffold.register(&registered).is_err());
+    // Registering a day in the middle lays everything out as rustfmt did, and keeps the
+    // days in order past streaming days.
+    let without_day9 = MOD_RS
+        .replace("mod day9;\n", "")
+        .replace(" day9::Day9,", "")
+        .replace("        Day::of::<Day9>(),\n", "");
+    assert_eq!(Scaffold::new(9).register(&without_day9).unwrap(), MOD_RS);
+
+    let source = scaffold.day_source();
+    assert!(source.contains("Day15 = day 15 -> (u64, String);"));
+    assert!(source.contains("example: r##\"1163\n\"#x\n\"## => (40, _),"));
+    assert_eq!(
+        scaffold.bin_source(),
+        "aoc2021::main!(aoc2021::days::Day15);\n"
+    );
+}
+
+#[test]
+fn test_scaffold_write() {
+    let root = crate::test_dir("scaffold");
+    let inputs = InputCache::new(root.join("inputs"));
+    let mod_path = root.join("src/days/mod.rs");
+    fs::create_dir_all(mod_path.parent().unwrap()).unwrap();
+    fs::write(&mod_path, MOD_RS).unwrap();
+    let scaffold = Scaffold {
+        example: Some("1163\n".into()),
+        ..Scaffold::new(15)
+    };
+
+    // The binary cannot be written, so the day file is removed again.
+    fs::write(root.join("src/bin"), "").unwrap();
+    assert!(scaffold.write(&root, &inputs).is_err());
+    assert!(!root.join("src/days/day15.rs").exists());
+    assert_eq!(fs::read_to_string(&mod_path).unwrap(), MOD_RS);
+
+    fs::remove_file(root.join("src/bin")).unwrap();
+    let written = scaffold.write(&root, &inputs).unwrap();
+    assert_eq!(
+        written,
+        [
+            root.join("src/days/day15.rs"),
+            root.join("src/bin/day15.rs"),
+            inputs.example_path(2021, 15),
+            mod_path.clone(),
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(&written[0]).unwrap(),
+        scaffold.day_source()
+    );
+    assert_eq!(fs::read_to_string(&written[2]).unwrap(), "1163\n");
+    assert!(fs::read_to_string(&mod_path)
+        .unwrap()
+        .contains("mod day15;"));
+    let error = scaffold.write(&root, &inputs).unwrap_err();
+    assert!(error.to_string().contains("already exists"));
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[allow(unnameable_test_items)]
+fn test_day_source_compiles() {
+    let scaffold = Scaffold {
+        day: 15,
+        out: ["u64".into(), "String".into()],
+        example: Some("1163\n\"#x\n".into()),
+        answers: [Some("40".into()), None],
+    };
+    assert_eq!(scaffold.day_source(), include_str!("scaffold/day15.rs"));
+    // The same text, compiled. Inside a function, the example test it declares is not
+    // run, as the day is not solved yet.
+    #[allow(dead_code)]
+    mod scaffolded {
+        include!("scaffold/day15.rs");
+    }
+    use scaffolded::Day15;
+    let examples = <Day15 as crate::Solution>::examples();
+    assert_eq!(examples[0].part1.as_deref(), Some("40"));
+    let error = <Day15 as crate::Solution>::solve(examples[0].input.into()).unwrap_err();
+    assert_eq!(error.to_string(), "not solved yet");
+}
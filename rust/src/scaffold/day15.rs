@@ -0,0 +1,14 @@
+use anyhow::bail;
+
+crate::day! {
+    Day15 = day 15 -> (u64, String);
+    examples {
+        example: r##"1163
+"#x
+"## => (40, _),
+    }
+    fn solve(input) {
+        let _ = input;
+        bail!("not solved yet")
+    }
+}
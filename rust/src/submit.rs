@@ -0,0 +1,224 @@
+use std::{fmt, time::Duration};
+
+use anyhow::{ensure, Context, Result};
+
+use crate::input;
+
+/// The site's verdict on a submitted answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about the direction.
+    Wrong,
+    /// An answer was submitted too recently; nothing was checked.
+    RateLimited(Duration),
+    /// The part was already solved, or is not unlocked yet.
+    AlreadySolved,
+    /// A response we don't recognize, as plain text.
+    Unknown(String),
+}
+
+impl Outcome {
+    /// Classify the html page returned by the answer endpoint.
+    pub fn parse(html: &str) -> Self {
+        let text = article_text(html);
+        if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Self::TooHigh
+            } else if text.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            }
+        } else if text.contains("You gave an answer too recently") {
+            match parse_wait(&text) {
+                Some(wait) => Self::RateLimited(wait),
+                None => Self::Unknown(text),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Self::AlreadySolved
+        } else {
+            Self::Unknown(text)
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong (too high)"),
+            Self::TooLow => write!(f, "wrong (too low)"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited(wait) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+            Self::AlreadySolved => write!(f, "already solved"),
+            Self::Unknown(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+/// Submit an answer for the given year, day and part (`level` 1 or 2).
+///
+/// Uses the same `AOC_SESSION_COOKIE` and `AOC_BASE_URL` as [`get_input`](crate::get_input).
+pub fn submit_answer(year: u16, day: u8, level: u8, answer: &str) -> Result<Outcome> {
+    input::load_dotenv();
+    let cookie = input::session_cookie()?;
+    submit_answer_to(&input::base_url(), &cookie, year, day, level, answer)
+}
+
+/// Submit an answer to the site at `base_url`, with `cookie` as the `Cookie` header.
+pub fn submit_answer_to(
+    base_url: &str,
+    cookie: &str,
+    year: u16,
+    day: u8,
+    level: u8,
+    answer: &str,
+) -> Result<Outcome> {
+    ensure!(level == 1 || level == 2, "invalid part {}", level);
+    ensure!(!answer.is_empty(), "cannot submit an empty answer");
+    ensure!(
+        !answer.contains('\n'),
+        "cannot submit an answer spanning multiple lines; read it and submit by hand"
+    );
+
+    let url = format!("{}/{}/day/{}/answer", base_url, year, day);
+    let html = ureq::post(&url)
+        .set("Cookie", cookie)
+        .send_form(&[("level", &level.to_string()), ("answer", answer)])
+        .context("http request error")?
+        .into_string()
+        .context("http response error")?;
+    Ok(Outcome::parse(&html))
+}
+
+/// The text of the page's `<article>` (or the whole page), without tags
+/// and with whitespace collapsed.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map(|(_, rest)| rest.split("</article>").next().unwrap_or(rest))
+        .unwrap_or(html);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse e.g. "You have 1m 5s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .try_fold(Duration::ZERO, |total, part| {
+            let (n, unit) = part.split_at(part.len().checked_sub(1)?);
+            let n: u64 = n.parse().ok()?;
+            let secs = match unit {
+                "h" => n * 3600,
+                "m" => n * 60,
+                "s" => n,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(secs))
+        })
+}
+
+#[test]
+fn test_parse_outcome() {
+    let page = |p: &str| format!("<html><main><article><p>{}</p></article></main></html>", p);
+    assert_eq!(
+        Outcome::parse(&page(
+            "That's the right answer! You are <em>one gold star</em> closer."
+        )),
+        Outcome::Correct
+    );
+    assert_eq!(
+        Outcome::parse(&page(
+            "That's not the right answer; your answer is too high."
+        )),
+        Outcome::TooHigh
+    );
+    assert_eq!(
+        Outcome::parse(&page(
+            "That's not the right answer; your answer is too low."
+        )),
+        Outcome::TooLow
+    );
+    assert_eq!(
+        Outcome::parse(&page("That's not the right answer. If you're stuck...")),
+        Outcome::Wrong
+    );
+    assert_eq!(
+        Outcome::parse(&page(
+            "You gave an answer too recently. You have 1m 5s left to wait."
+        )),
+        Outcome::RateLimited(Duration::from_secs(65))
+    );
+    assert_eq!(
+        Outcome::parse(&page(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        )),
+        Outcome::AlreadySolved
+    );
+    assert!(matches!(Outcome::parse("<p>hi</p>"), Outcome::Unknown(_)));
+}
+
+#[test]
+fn test_submit_to_local_server() {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = len.trim().parse().unwrap();
+            }
+            if line == "\r\n" {
+                break;
+            }
+            head.push_str(&line);
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        let html = "<article><p>That's not the right answer; your answer is too low.</p></article>";
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            html.len(),
+            html
+        )
+        .unwrap();
+        (head, String::from_utf8(body).unwrap())
+    });
+
+    let outcome = submit_answer_to(&base_url, "session=abc", 2021, 7, 2, "1234").unwrap();
+    assert_eq!(outcome, Outcome::TooLow);
+
+    let (head, body) = server.join().unwrap();
+    assert!(head.starts_with("POST /2021/day/7/answer HTTP/1.1"));
+    assert!(head.contains("session=abc"));
+    assert_eq!(body, "level=2&answer=1234");
+}
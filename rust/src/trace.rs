@@ -0,0 +1,202 @@
+//! Tracing of intermediate solver state, for debugging a wrong answer.
+//!
+//! Solvers emit traces with [`trace!`](crate::trace!). They are off unless a level is set
+//! with `--trace LEVEL` or the environment variable `AOC_TRACE`, or they are being
+//! [`capture`]d. When off, a trace costs one atomic load, and its arguments are not
+//! evaluated.
+
+use std::{
+    cell::RefCell,
+    fmt::{self, Write},
+    str::FromStr,
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+};
+
+use anyhow::{bail, Error};
+
+/// How detailed a trace is. Enabling a level also enables the less detailed ones.
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    /// A few lines per solve, such as the result of each phase.
+    Info = 1,
+    /// A line per step, such as counts.
+    Debug = 2,
+    /// Whole states per step, such as grids.
+    Trace = 3,
+}
+
+impl FromStr for Level {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(Self::Info),
+            "debug" => Ok(Self::Debug),
+            "trace" => Ok(Self::Trace),
+            _ => bail!("invalid trace level '{}', expected info, debug or trace", s),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Debug => write!(f, "debug"),
+            Self::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+/// The most detailed level which is enabled anywhere: the global level, or that of
+/// any capture in progress. `UNSET` until the environment has been read.
+static ENABLED: AtomicU8 = AtomicU8::new(UNSET);
+static GLOBAL: AtomicU8 = AtomicU8::new(UNSET);
+static CAPTURES: AtomicUsize = AtomicUsize::new(0);
+const UNSET: u8 = u8::MAX;
+const OFF: u8 = 0;
+
+thread_local! {
+    static CAPTURE: RefCell<Option<(Level, String)>> = RefCell::new(None);
+}
+
+/// Set the global level, overriding `AOC_TRACE`; `None` turns tracing off.
+pub fn set_level(level: Option<Level>) {
+    GLOBAL.store(level.map_or(OFF, |l| l as u8), Ordering::Relaxed);
+    update_enabled();
+}
+
+/// Whether traces at `level` are wanted, on this thread.
+#[inline]
+pub fn enabled(level: Level) -> bool {
+    let enabled = ENABLED.load(Ordering::Relaxed);
+    enabled >= level as u8 && (enabled != UNSET || init()) && enabled_slow(level)
+}
+
+#[cold]
+fn init() -> bool {
+    if GLOBAL.load(Ordering::Relaxed) == UNSET {
+        crate::input::load_dotenv();
+        let level = std::env::var("AOC_TRACE").ok().and_then(|level| {
+            level
+                .parse::<Level>()
+                .map_err(|e| eprintln!("WARN: ignoring AOC_TRACE: {}", e))
+                .ok()
+        });
+        let level = level.map_or(OFF, |l| l as u8);
+        let _ = GLOBAL.compare_exchange(UNSET, level, Ordering::Relaxed, Ordering::Relaxed);
+    }
+    update_enabled();
+    true
+}
+
+fn enabled_slow(level: Level) -> bool {
+    GLOBAL.load(Ordering::Relaxed) >= level as u8
+        || CAPTURE.with(|capture| matches!(&*capture.borrow(), Some((l, _)) if *l >= level))
+}
+
+fn update_enabled() {
+    let global = match GLOBAL.load(Ordering::Relaxed) {
+        UNSET => OFF,
+        global => global,
+    };
+    let capturing = match CAPTURES.load(Ordering::Relaxed) {
+        0 => OFF,
+        _ => Level::Trace as u8,
+    };
+    ENABLED.store(global.max(capturing), Ordering::Relaxed);
+}
+
+/// Write a trace; use [`trace!`](crate::trace!) instead, which checks [`enabled`] first.
+#[doc(hidden)]
+pub fn emit(level: Level, args: fmt::Arguments) {
+    let captured = CAPTURE.with(|capture| match &mut *capture.borrow_mut() {
+        Some((max, buffer)) if *max >= level => {
+            let _ = writeln!(buffer, "[{}] {}", level, args);
+            true
+        }
+        _ => false,
+    });
+    if !captured {
+        eprintln!("[{}] {}", level, args);
+    }
+}
+
+/// Run `f`, collecting its traces up to `level` on this thread instead of printing them.
+///
+/// If `f` panics, what it traced is printed before the panic continues.
+pub fn capture<T>(level: Level, f: impl FnOnce() -> T) -> (T, String) {
+    let mut guard = Capture::start(level);
+    let value = f();
+    (value, guard.finish())
+}
+
+/// A capture in progress, which ends when dropped, even by a panic.
+struct Capture {
+    previous: Option<Option<(Level, String)>>,
+}
+
+impl Capture {
+    fn start(level: Level) -> Self {
+        let previous = CAPTURE.with(|capture| capture.replace(Some((level, String::new()))));
+        CAPTURES.fetch_add(1, Ordering::Relaxed);
+        update_enabled();
+        Self {
+            previous: Some(previous),
+        }
+    }
+    /// End the capture, restoring any enclosing one, and return the trace.
+    fn finish(&mut self) -> String {
+        let previous = match self.previous.take() {
+            Some(previous) => previous,
+            None => return String::new(),
+        };
+        CAPTURES.fetch_sub(1, Ordering::Relaxed);
+        update_enabled();
+        CAPTURE
+            .with(|capture| capture.replace(previous))
+            .map(|(_, trace)| trace)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        let trace = self.finish();
+        if std::thread::panicking() && !trace.is_empty() {
+            eprint!("Trace before the panic:\n{}", trace);
+        }
+    }
+}
+
+/// Emit a trace at a [`Level`], formatted like `format!`, if that level is enabled.
+///
+/// ```ignore
+/// crate::trace!(Debug, "step {}: {} flashes", step, flashes);
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, $($arg:tt)+) => {
+        if $crate::trace::enabled($crate::trace::Level::$level) {
+            $crate::trace::emit($crate::trace::Level::$level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[test]
+fn test_capture() {
+    let ((), trace) = capture(Level::Debug, || {
+        crate::trace!(Debug, "step {}", 1);
+        crate::trace!(Trace, "not captured");
+    });
+    assert_eq!(trace, "[debug] step 1\n");
+    // A panic ends the capture.
+    let panicked = std::panic::catch_unwind(|| {
+        capture(Level::Trace, || {
+            crate::trace!(Info, "before");
+            panic!("solver bug");
+        })
+    });
+    assert!(panicked.is_err());
+    assert!(CAPTURE.with(|capture| capture.borrow().is_none()));
+    assert!("bogus".parse::<Level>().is_err());
+}
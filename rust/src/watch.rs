@@ -0,0 +1,253 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Result;
+
+use crate::{days::Day, Example, InputCache, InputSource, Normalize, Options, Record};
+
+/// How often [`watch`] checks the files by default.
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Solve `day` on its real input and every saved example (`dayNN.example*.txt`), then
+/// again whenever any of those files change, printing how the answers changed.
+///
+/// Each run also checks the examples in the day's source (see [`Day::examples`]) and
+/// prints whether they pass. Known answers (`verify` and `lock`) only apply to the real
+/// input. Files are polled every `interval`. Runs until the process is stopped.
+pub fn watch(day: Day, options: &Options, interval: Duration) -> Result<()> {
+    let name = format!("{} Day {:02}", day.year, day.day);
+    let real = options.input_cache().path(day.year, day.day);
+    let mut previous = BTreeMap::new();
+    let mut seen = BTreeMap::new();
+    let mut options = options.clone();
+    let mut run = 0;
+    loop {
+        let examples = example_paths(&InputCache::from_env(), day.year, day.day);
+        let watched: Vec<PathBuf> = examples.iter().chain([&real]).cloned().collect();
+        let files = modified_times(&watched);
+        if files != seen {
+            run += 1;
+            let changed: Vec<String> = changed(&seen, &files)
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            match run {
+                1 => println!("{} - Run 1, watching {}", name, changed.join(", ")),
+                _ => println!("{} - Run {}, changed {}", name, run, changed.join(", ")),
+            }
+            let runs = [(String::from("input"), InputSource::Real)]
+                .into_iter()
+                .chain(examples.iter().map(|path| {
+                    let label = path.file_stem().unwrap_or_default().to_string_lossy();
+                    (label.into_owned(), InputSource::File(path.clone()))
+                }));
+            for (label, input) in runs {
+                // The known answers are those of the real input.
+                let real = input == InputSource::Real;
+                let record = day.record(&Options {
+                    input,
+                    verify: options.verify && real,
+                    lock: options.lock && real,
+                    ..options.clone()
+                });
+                println!(
+                    "  {:<16} {}",
+                    label,
+                    describe(previous.get(&label), &record)
+                );
+                previous.insert(label, record);
+            }
+            for example in day.examples() {
+                let label = format!("{} (source)", example.name);
+                println!("  {:<16} {}", label, check_example(&day, &example));
+            }
+            // The real input is downloaded at most once.
+            options.refresh = false;
+            // Don't run again for the download, or anything else done by this run.
+            seen = modified_times(&watched);
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// The saved examples for a day: `dayNN.example.txt`, then any others such as
+/// `dayNN.example2.txt`, in order.
+fn example_paths(cache: &InputCache, year: u16, day: u8) -> Vec<PathBuf> {
+    let first = cache.example_path(year, day);
+    let prefix = format!("day{:02}.example", day);
+    let mut paths: Vec<PathBuf> = first
+        .parent()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.starts_with(&prefix) && name.ends_with(".txt")
+        })
+        .collect();
+    paths.sort_by_key(|path| (*path != first, path.clone()));
+    paths
+}
+
+/// The files which were added, removed or modified since `seen`.
+fn changed<'a>(
+    seen: &'a BTreeMap<PathBuf, Option<SystemTime>>,
+    files: &'a BTreeMap<PathBuf, Option<SystemTime>>,
+) -> Vec<&'a PathBuf> {
+    let mut changed: Vec<&PathBuf> = files
+        .iter()
+        .filter(|&(path, time)| seen.get(path) != Some(time))
+        .chain(seen.iter().filter(|&(path, _)| !files.contains_key(path)))
+        .map(|(path, _)| path)
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// When each file which exists was last modified.
+fn modified_times(paths: &[PathBuf]) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(path).ok()?;
+            Some((path.clone(), metadata.modified().ok()))
+        })
+        .collect()
+}
+
+/// The answers, time and verdicts of a run on one line, with each answer which changed
+/// since the previous run shown as `old -> new`.
+fn describe(previous: Option<&Record>, record: &Record) -> String {
+    if let Some(error) = &record.error {
+        return format!("error: {}", error);
+    }
+    let answer = |answer: &Option<String>| answer.as_deref().unwrap_or("-").replace('\n', "⏎");
+    let parts: Vec<String> = [(&record.part1, 1), (&record.part2, 2)]
+        .iter()
+        .map(|&(part, level)| {
+            let old = previous
+                .filter(|previous| previous.error.is_none())
+                .map(|previous| [&previous.part1, &previous.part2][level - 1]);
+            match old {
+                Some(old) if old != part => {
+                    format!("part {}: {} -> {}", level, answer(old), answer(part))
+                }
+                _ => format!("part {}: {}", level, answer(part)),
+            }
+        })
+        .collect();
+    let time = record.timings.map_or(Duration::ZERO, |t| t.total);
+    let mut line = format!("{} | {:.2?}", parts.join(" | "), time);
+    for (level, verdict) in (1..=2).zip(&record.verdicts) {
+        if let Some(verdict) = verdict {
+            line.push_str(&format!(" | part {} {}", level, verdict));
+        }
+    }
+    line
+}
+
+/// Whether the answers to an example from the day's source are right, on one line.
+fn check_example(day: &Day, example: &Example<String, String>) -> String {
+    let (input, _) = Normalize::default().apply(example.input);
+    let (part1, part2) = match panic::catch_unwind(AssertUnwindSafe(|| day.solve(input))) {
+        Ok(Ok(answers)) => answers,
+        Ok(Err(e)) => return format!("FAIL | error: {:#}", e),
+        Err(payload) => return format!("FAIL | panicked: {}", crate::panic_message(&*payload)),
+    };
+    let mut pass = true;
+    let parts: Vec<String> = [(1, &example.part1, part1), (2, &example.part2, part2)]
+        .into_iter()
+        .map(|(level, expected, answer)| {
+            let show = |answer: &str| answer.replace('\n', "⏎");
+            match expected {
+                Some(expected) if *expected != answer => {
+                    pass = false;
+                    format!(
+                        "part {}: {}, expected {}",
+                        level,
+                        show(&answer),
+                        show(expected)
+                    )
+                }
+                _ => format!("part {}: {}", level, show(&answer)),
+            }
+        })
+        .collect();
+    let verdict = if pass { "PASS" } else { "FAIL" };
+    format!("{} | {}", verdict, parts.join(" | "))
+}
+
+#[test]
+fn test_example_paths() {
+    let dir = crate::test_dir("watch");
+    fs::create_dir_all(dir.join("2021")).unwrap();
+    for name in [
+        "day07.txt",
+        "day07.example2.txt",
+        "day07.example.txt",
+        "day07.example.bak",
+        "day08.example.txt",
+    ] {
+        fs::write(dir.join("2021").join(name), "1\n").unwrap();
+    }
+    let cache = InputCache::new(&dir);
+    let paths = example_paths(&cache, 2021, 7);
+    let names: Vec<_> = paths.iter().map(|p| p.file_name().unwrap()).collect();
+    assert_eq!(names, ["day07.example.txt", "day07.example2.txt"]);
+    assert!(example_paths(&cache, 2021, 9).is_empty());
+
+    let real = cache.path(2021, 7);
+    let missing = cache.path(2021, 9);
+    let watched: Vec<PathBuf> = paths.iter().chain([&real, &missing]).cloned().collect();
+    let seen = modified_times(&watched);
+    assert_eq!(seen.len(), 3);
+    assert!(changed(&seen, &seen).is_empty());
+    let mut files = seen.clone();
+    files.insert(paths[1].clone(), Some(SystemTime::UNIX_EPOCH));
+    files.remove(&real);
+    files.insert(missing.clone(), None);
+    assert_eq!(changed(&seen, &files), [&paths[1], &real, &missing]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_describe() {
+    let mut old = Record::new(2021, 7);
+    old.part1 = Some("37".into());
+    old.part2 = Some("168".into());
+    let mut new = old.clone();
+    new.part2 = Some("a\nb".into());
+    assert_eq!(describe(None, &old), "part 1: 37 | part 2: 168 | 0.00ns");
+    assert_eq!(
+        describe(Some(&old), &new),
+        "part 1: 37 | part 2: 168 -> a⏎b | 0.00ns"
+    );
+    new.error = Some("oops".into());
+    assert_eq!(describe(Some(&old), &new), "error: oops");
+}
+
+#[test]
+fn test_check_example() {
+    let day = crate::days::get(2021, 7).unwrap();
+    let mut examples = day.examples();
+    assert_eq!(examples[0].part2.as_deref(), Some("168"));
+    assert_eq!(
+        check_example(&day, &examples[0]),
+        "PASS | part 1: 37 | part 2: 168"
+    );
+    examples[0].part1 = Some("38".into());
+    examples[0].part2 = None;
+    assert_eq!(
+        check_example(&day, &examples[0]),
+        "FAIL | part 1: 37, expected 38 | part 2: 168"
+    );
+    examples[0].input = "x";
+    assert!(check_example(&day, &examples[0]).starts_with("FAIL | error: "));
+}